@@ -0,0 +1,95 @@
+use anyhow::{Context, Result};
+use std::fs;
+use std::io::ErrorKind;
+
+/// Marks the start and end of a generated block so it can be replaced on the next run
+/// without touching anything written by hand.
+fn markers(name: &str) -> (String, String) {
+    (
+        format!("<!-- ja:{}:start -->", name),
+        format!("<!-- ja:{}:end -->", name),
+    )
+}
+
+/// Replaces the named generated block with `body`, or appends a new block at the end
+/// if the text doesn't have one yet.
+pub fn upsert_block(contents: &str, name: &str, body: &str) -> String {
+    let (start, end) = markers(name);
+    let block = format!("{}\n{}\n{}", start, body.trim_end_matches('\n'), end);
+
+    if let Some(start_index) = contents.find(&start) {
+        if let Some(end_offset) = contents[start_index..].find(&end) {
+            let end_index = start_index + end_offset + end.len();
+            return format!(
+                "{}{}{}",
+                &contents[..start_index],
+                block,
+                &contents[end_index..]
+            );
+        }
+    }
+
+    let mut updated = contents.trim_end_matches('\n').to_string();
+    if !updated.is_empty() {
+        updated.push_str("\n\n");
+    }
+    updated.push_str(&block);
+    updated.push('\n');
+    updated
+}
+
+/// Upserts the named block in a file (creating the file if needed). Returns whether it changed.
+pub fn write_block(path: &str, name: &str, body: &str) -> Result<bool> {
+    let contents = match fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(e) if e.kind() == ErrorKind::NotFound => String::new(),
+        Err(e) => return Err(e).with_context(|| format!("Failed to read {}", path)),
+    };
+    let new_contents = upsert_block(&contents, name, body);
+    if new_contents == contents {
+        return Ok(false);
+    }
+    fs::write(path, new_contents)?;
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_upsert_block() {
+        let contents = "# Friday, March 15, 2024\n\nWent for a walk.\n";
+
+        let first = upsert_block(contents, "health", "steps: 100");
+        assert_eq!(
+            first,
+            "# Friday, March 15, 2024\n\nWent for a walk.\n\n<!-- ja:health:start -->\nsteps: 100\n<!-- ja:health:end -->\n"
+        );
+
+        // A second run replaces the block and keeps the surrounding text
+        let edited = first.replace("Went for a walk.", "Went for a long walk.") + "\nLater notes\n";
+        let second = upsert_block(&edited, "health", "steps: 200");
+        assert!(second.contains("Went for a long walk."));
+        assert!(second.ends_with("<!-- ja:health:end -->\n\nLater notes\n"));
+        assert_eq!(second.matches("ja:health:start").count(), 1);
+        assert!(second.contains("<!-- ja:health:start -->\nsteps: 200\n"));
+    }
+
+    #[test]
+    fn test_write_block_read_errors() {
+        let dir = std::env::temp_dir().join(format!("ja-block-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+
+        // A missing file is created with just the block
+        let path = dir.join("entry.md");
+        let path = path.to_str().unwrap();
+        assert!(write_block(path, "health", "steps: 100").unwrap());
+        assert!(fs::read_to_string(path).unwrap().contains("steps: 100"));
+
+        // Any other read error (here a directory) is reported instead of overwriting
+        assert!(write_block(dir.to_str().unwrap(), "health", "steps: 100").is_err());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
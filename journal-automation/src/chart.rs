@@ -0,0 +1,216 @@
+use chrono::{Datelike, Duration, NaiveDate};
+use std::collections::BTreeMap;
+
+const SPARKS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+const SHADES: [char; 5] = ['·', '░', '▒', '▓', '█'];
+
+/// Width of the attached terminal, then `COLUMNS`, falling back to 80.
+pub fn terminal_width() -> usize {
+    terminal_size::terminal_size()
+        .map(|(terminal_size::Width(w), _)| w as usize)
+        .or_else(|| std::env::var("COLUMNS").ok().and_then(|c| c.parse().ok()))
+        .filter(|&c: &usize| c >= 40)
+        .unwrap_or(80)
+}
+
+/// A progress bar such as `█████░░░░░` for a percentage (0-100).
+pub fn bar(percent: f64, width: usize) -> String {
+    let filled = ((percent.clamp(0.0, 100.0) / 100.0) * width as f64).round() as usize;
+    "█".repeat(filled) + &"░".repeat(width - filled)
+}
+
+/// One character per value, scaled between the smallest and largest value.
+pub fn sparkline(values: &[f64]) -> String {
+    let min = values.iter().copied().fold(f64::INFINITY, f64::min);
+    let max = values.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    values
+        .iter()
+        .map(|&v| {
+            if max > min {
+                SPARKS[(((v - min) / (max - min)) * 7.0).round() as usize]
+            } else {
+                SPARKS[3]
+            }
+        })
+        .collect()
+}
+
+/// Horizontal bars with their labels and values, scaled so the longest fits the terminal.
+pub fn bar_chart(items: &[(String, f64)], precision: usize) -> Vec<String> {
+    let label_width = items
+        .iter()
+        .map(|(l, _)| l.chars().count())
+        .max()
+        .unwrap_or(0);
+    let values: Vec<String> = items
+        .iter()
+        .map(|(_, v)| format!("{:.precision$}", v))
+        .collect();
+    let value_width = values.iter().map(|v| v.len()).max().unwrap_or(0);
+    let max = items.iter().map(|(_, v)| *v).fold(0.0, f64::max);
+    let width = terminal_width()
+        .saturating_sub(label_width + value_width + 5)
+        .clamp(10, 60);
+
+    items
+        .iter()
+        .zip(&values)
+        .map(|((label, value), text)| {
+            let length = if max > 0.0 {
+                ((value / max) * width as f64).round() as usize
+            } else {
+                0
+            };
+            format!(
+                "{:<label_width$} │{}{} {:>value_width$}",
+                label,
+                "█".repeat(length),
+                " ".repeat(width - length),
+                text
+            )
+        })
+        .collect()
+}
+
+/// Plots values as points on a grid with a y-axis scale and the first and last labels below.
+pub fn line_chart(items: &[(String, f64)], height: usize) -> Vec<String> {
+    if items.is_empty() || height < 2 {
+        return Vec::new();
+    }
+    let min = items.iter().map(|(_, v)| *v).fold(f64::INFINITY, f64::min);
+    let max = items
+        .iter()
+        .map(|(_, v)| *v)
+        .fold(f64::NEG_INFINITY, f64::max);
+    let span = if max > min { max - min } else { 1.0 };
+
+    // Squeeze long series into the terminal by averaging neighbouring points
+    let columns = terminal_width().saturating_sub(9).max(10);
+    let per_column = items.len().div_ceil(columns);
+    let points: Vec<f64> = items
+        .chunks(per_column)
+        .map(|chunk| chunk.iter().map(|(_, v)| v).sum::<f64>() / chunk.len() as f64)
+        .collect();
+    let rows: Vec<usize> = points
+        .iter()
+        .map(|v| (((v - min) / span) * (height - 1) as f64).round() as usize)
+        .collect();
+
+    let mut lines = Vec::new();
+    for row in (0..height).rev() {
+        let scale = if row == height - 1 {
+            format!("{:>7.0}", max)
+        } else if row == 0 {
+            format!("{:>7.0}", min)
+        } else {
+            " ".repeat(7)
+        };
+        let plot: String = rows
+            .iter()
+            .map(|&r| if r == row { '•' } else { ' ' })
+            .collect();
+        lines.push(format!("{} │{}", scale, plot.trim_end()));
+    }
+    lines.push(format!("{} └{}", " ".repeat(7), "─".repeat(points.len())));
+
+    let first = &items[0].0;
+    let last = &items[items.len() - 1].0;
+    let gap = points.len().saturating_sub(first.len() + last.len()).max(1);
+    lines.push(format!(
+        "{}  {}{}{}",
+        " ".repeat(7),
+        first,
+        " ".repeat(gap),
+        last
+    ));
+    lines
+}
+
+/// A calendar heatmap with a row per weekday and a column per week, shading each day by its
+/// value relative to the largest. Days without a value are left blank.
+pub fn heatmap(values: &BTreeMap<NaiveDate, f64>) -> Vec<String> {
+    let (Some((&first, _)), Some((&last, _))) = (values.first_key_value(), values.last_key_value())
+    else {
+        return Vec::new();
+    };
+    let max = values.values().copied().fold(0.0, f64::max);
+    let start = first - Duration::days(first.weekday().num_days_from_monday() as i64);
+    let weeks = ((last - start).num_days() / 7 + 1) as usize;
+
+    // Month names above the week each month starts in, skipped if they would overlap
+    let mut header = vec![' '; weeks + 3];
+    for week in 0..weeks {
+        let monday = start + Duration::days(week as i64 * 7);
+        let month_start = (0..7)
+            .map(|d| monday + Duration::days(d))
+            .find(|d| (d.day() == 1 && *d <= last) || (week == 0 && *d == first));
+        if let Some(date) = month_start {
+            if header[week.saturating_sub(1)..week + 3]
+                .iter()
+                .all(|c| *c == ' ')
+            {
+                for (i, c) in date.format("%b").to_string().chars().enumerate() {
+                    header[week + i] = c;
+                }
+            }
+        }
+    }
+    let mut lines = vec![format!(
+        "    {}",
+        header.iter().collect::<String>().trim_end()
+    )];
+
+    for (weekday, name) in ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"]
+        .iter()
+        .enumerate()
+    {
+        let row: String = (0..weeks)
+            .map(|week| {
+                let date = start + Duration::days((week * 7 + weekday) as i64);
+                match values.get(&date) {
+                    Some(v) if max > 0.0 => SHADES[((v / max) * 4.0).round() as usize],
+                    Some(_) => SHADES[0],
+                    None => ' ',
+                }
+            })
+            .collect();
+        lines.push(format!("{} {}", name, row.trim_end()));
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bar_and_sparkline() {
+        assert_eq!(bar(50.0, 10), "█████░░░░░");
+        assert_eq!(bar(120.0, 4), "████");
+        assert_eq!(sparkline(&[1.0, 5.0, 8.0]), "▁▅█");
+        assert_eq!(sparkline(&[2.0, 2.0]), "▄▄");
+    }
+
+    #[test]
+    fn test_bar_chart_aligns_labels_and_values() {
+        let items = vec![("2023".to_string(), 100.0), ("2024".to_string(), 50.0)];
+        let lines = bar_chart(&items, 1);
+        assert!(lines[0].starts_with("2023 │█"));
+        assert!(lines[0].ends_with(" 100.0"));
+        assert!(lines[1].ends_with("  50.0"));
+        assert_eq!(lines[0].chars().count(), lines[1].chars().count());
+    }
+
+    #[test]
+    fn test_heatmap() {
+        let monday = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+        let values: BTreeMap<NaiveDate, f64> = (0..14)
+            .map(|i| (monday + Duration::days(i), (i % 7 % 2) as f64))
+            .collect();
+        let lines = heatmap(&values);
+        assert_eq!(lines.len(), 8);
+        assert!(lines[0].contains("Jan"));
+        assert_eq!(lines[1], "Mon ··");
+        assert_eq!(lines[2], "Tue ██");
+    }
+}
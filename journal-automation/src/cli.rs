@@ -1,5 +1,7 @@
-use crate::utils::validate_year;
-use clap::{Parser, Subcommand};
+use crate::journal::LengthGrouping;
+use crate::utils::{validate_month, validate_scale, validate_year};
+use chrono::NaiveDate;
+use clap::{Args, Parser, Subcommand};
 
 #[derive(Debug, Parser)]
 #[command(author, version, about, long_about = None)]
@@ -15,6 +17,12 @@ pub enum Commands {
         /// Class name (e.g., CS101)
         #[arg(long, default_value = "journal")]
         class: String,
+        /// Use the class in session right now, or the daily journal if there is none
+        #[arg(long, conflicts_with = "class")]
+        auto: bool,
+        /// Minutes before a class starts and after it ends that still count as class time
+        #[arg(long, default_value_t = 10, requires = "auto")]
+        grace_minutes: i64,
     },
     /// Open today's journal entry
     OpenJournal {
@@ -39,23 +47,240 @@ pub enum Commands {
         #[arg(default_value = "journal")]
         class: String,
     },
+    /// Show progress on a month's goals, measuring targets such as "run 50 miles" or "journal 25
+    /// days" against the journal and runs log, and check off the ones that are met
+    Goals {
+        /// Month in YYYY-MM format (defaults to the current month)
+        #[arg(value_parser = validate_month)]
+        month: Option<NaiveDate>,
+        /// Summarize every month of a year instead (2000-2099)
+        #[arg(long, value_parser = validate_year, conflicts_with = "month")]
+        year: Option<u32>,
+        /// Copy unfinished goals into next month's goals file
+        #[arg(long, conflicts_with = "year")]
+        rollover: bool,
+    },
+    /// Write a review of the year (completion, streak, words, tags, highlights and longest entries)
+    /// into its journey file
+    YearReview {
+        /// Year to review (2000-2099)
+        #[arg(value_parser = validate_year)]
+        year: u32,
+    },
+    /// Collect custom sections and #highlight lines into the month's Happenings file and check
+    /// off goals referenced with #goal/... tags
+    MonthSummary {
+        /// Month in YYYY-MM format (defaults to the current month)
+        #[arg(value_parser = validate_month)]
+        month: Option<NaiveDate>,
+    },
     /// Find and open a random empty journal entry
     EmptyDay {
         /// Optional year to limit search to (2000-2099)
         #[arg(value_parser = validate_year)]
         year: Option<u32>,
+        #[command(flatten)]
+        notebooks: NotebookArgs,
     },
     /// Add a custom header to today's journal entry
     AddCustomHeader {
         /// The header text to add
         header: String,
     },
+    /// Record today's mood (and optionally energy) from 1 to 5 in the entry's header table
+    Mood {
+        /// Mood from 1 (low) to 5 (great)
+        #[arg(value_parser = validate_scale)]
+        mood: u8,
+        /// Energy from 1 (drained) to 5 (energized)
+        #[arg(long, value_parser = validate_scale)]
+        energy: Option<u8>,
+    },
     /// Analyze journal completion rates
-    AnalyzeCompletion,
+    AnalyzeCompletion {
+        #[command(flatten)]
+        notebooks: NotebookArgs,
+    },
     /// Analyze journal length statistics
-    AnalyzeLength,
+    AnalyzeLength {
+        /// Group entries by year, month, ISO week or weekday
+        #[arg(long, value_enum, default_value_t = LengthGrouping::Year)]
+        by: LengthGrouping,
+        #[command(flatten)]
+        notebooks: NotebookArgs,
+    },
+    /// Relate recorded moods to the weekday, weather, sleep and class days
+    AnalyzeMood,
+    /// Compare class notes with scheduled sessions: completion, missed sessions and note length
+    AnalyzeClasses,
+    /// Write a self-contained HTML dashboard of completion, length, streaks, days written and tags
+    Report {
+        /// Folder to write index.html into
+        #[arg(long)]
+        html: String,
+        /// Only include this year (2000-2099)
+        #[arg(long, value_parser = validate_year)]
+        year: Option<u32>,
+        #[command(flatten)]
+        notebooks: NotebookArgs,
+    },
     /// Validate journal structure against expected dates
-    ValidateStructure,
+    ValidateStructure {
+        #[command(flatten)]
+        notebooks: NotebookArgs,
+    },
     /// Validate journal contents
-    ValidateContents,
+    ValidateContents {
+        #[command(flatten)]
+        notebooks: NotebookArgs,
+    },
+    /// Pick videos without repeating them too often
+    Video {
+        #[command(subcommand)]
+        command: VideoCommands,
+    },
+    /// Track conversion of paper journals into daily entries
+    Paper {
+        #[command(subcommand)]
+        command: PaperCommands,
+    },
+    /// Point Obsidian's daily notes folder at the current month
+    RolloverDailyNotes,
+    /// Export the templates and folder structure as a new repository without personal content
+    ExportTemplate {
+        /// Destination folder for the new repository
+        dest: String,
+        /// Remove locations and instructors from class schedules
+        #[arg(long)]
+        anonymize: bool,
+    },
+    /// Import sleep, workouts, steps and resting heart rate from an Apple Health export
+    ImportHealth {
+        /// Path to export.zip (or an extracted export.xml)
+        export: String,
+    },
+    /// Import GPX/TCX runs into daily entries and the yearly running total
+    ImportRuns {
+        /// Folder containing .gpx or .tcx files
+        dir: String,
+    },
+    /// Print an iCalendar (.ics) file with an all-day event for each written journal entry
+    ExportIcs {
+        /// Optional year to limit the export to (2000-2099)
+        #[arg(value_parser = validate_year)]
+        year: Option<u32>,
+        #[command(flatten)]
+        notebooks: NotebookArgs,
+    },
+    /// Show the class in progress or the next one across all schedules
+    Next {
+        /// Start or open today's notes for that class
+        #[arg(long)]
+        open: bool,
+    },
+    /// Manage class schedules
+    Schedule {
+        #[command(subcommand)]
+        command: ScheduleCommands,
+    },
+    /// Work with all classes in a term (semester) at once
+    Term {
+        #[command(subcommand)]
+        command: TermCommands,
+    },
+}
+
+/// Which notebooks a scanning command looks at; the daily journal by default.
+#[derive(Debug, Args)]
+pub struct NotebookArgs {
+    /// Class notebook to scan instead of the journal (e.g., CS101)
+    #[arg(long)]
+    pub class: Option<String>,
+    /// Scan the journal and every class notebook
+    #[arg(long, conflicts_with = "class")]
+    pub all: bool,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum VideoCommands {
+    /// Pick the next video and record it in today's journal entry
+    Next {
+        /// Path to the video list (defaults to videos/list.txt)
+        #[arg(long)]
+        list: Option<String>,
+        /// Number of days before a video may be shown again
+        #[arg(long, default_value_t = 14)]
+        window: u32,
+    },
+    /// Show how often each video has been shown
+    Stats {
+        /// Path to the video list (defaults to videos/list.txt)
+        #[arg(long)]
+        list: Option<String>,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum PaperCommands {
+    /// Record a paper journal volume and create placeholder files for its days
+    Add {
+        /// Name of the paper volume (e.g., "Blue notebook")
+        name: String,
+        /// First date in the volume (YYYY-MM-DD)
+        start_date: String,
+        /// Last date in the volume (YYYY-MM-DD)
+        end_date: String,
+    },
+    /// Show how many days of each paper volume remain to be transcribed
+    Status,
+    /// Open the next untranscribed days in date order
+    Next {
+        /// Number of days to open
+        #[arg(long, default_value_t = 1)]
+        pages: usize,
+        /// Only take days from this paper volume
+        #[arg(long)]
+        volume: Option<String>,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum ScheduleCommands {
+    /// Create a class schedule from an iCalendar (.ics) file
+    Import {
+        /// Path to the .ics file
+        file: String,
+        /// Class name (defaults to the first word of the event summary)
+        #[arg(long)]
+        class: Option<String>,
+        /// Overwrite an existing schedule file
+        #[arg(long)]
+        force: bool,
+    },
+    /// Validate schedule times and dates and report overlapping classes
+    Check {
+        /// Warn when consecutive classes in different buildings are closer than this (minutes)
+        #[arg(long, default_value_t = 15)]
+        travel_minutes: i64,
+    },
+    /// Print a class schedule as iCalendar (.ics) weekly events
+    Export {
+        /// Class name (e.g., CS101)
+        #[arg(long)]
+        class: String,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum TermCommands {
+    /// Create notes files for every class day of every class in the term
+    CreateNotes {
+        /// Term name (e.g., "Spring 2024"); defaults to the current term
+        name: Option<String>,
+    },
+    /// Show how many class days have notes for each class in the term
+    Report {
+        /// Term name (e.g., "Spring 2024"); defaults to the current term
+        name: Option<String>,
+    },
 }
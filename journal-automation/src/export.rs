@@ -0,0 +1,162 @@
+use crate::journal::create_year_at;
+use crate::schedule::ClassSchedule;
+use crate::utils::{get_git_root, DEVICE_MAC, DEVICE_NAME};
+use crate::{paper, runs, video};
+use anyhow::{Context, Result};
+use chrono::{Datelike, Local};
+use std::{fs, path::Path, process::Command};
+
+// Folders inside journal-automation that hold build output or personal data. Health data is written
+// into the entries themselves, so it has no folder of its own.
+const SKIPPED_DIRS: [&str; 4] = ["target", runs::DATA_DIR, video::DATA_DIR, paper::DATA_DIR];
+
+const PLACEHOLDER_MAC: &str = "00:00:00:00:00:00";
+const PLACEHOLDER_DEVICE: &str = "my device";
+
+fn copy_dir(from: &Path, to: &Path, skipped: &[&str]) -> Result<()> {
+    for entry in walkdir::WalkDir::new(from)
+        .into_iter()
+        .filter_entry(|e| {
+            e.depth() == 0 || !skipped.iter().any(|s| e.file_name().to_str() == Some(*s))
+        })
+        .filter_map(|e| e.ok())
+    {
+        let relative = entry.path().strip_prefix(from)?;
+        let target = to.join(relative);
+        if entry.file_type().is_dir() {
+            fs::create_dir_all(&target)?;
+        } else {
+            fs::copy(entry.path(), &target)
+                .with_context(|| format!("Failed to copy {}", entry.path().display()))?;
+        }
+    }
+
+    Ok(())
+}
+
+fn anonymize_schedules(schedules_dir: &Path) -> Result<()> {
+    if !schedules_dir.exists() {
+        return Ok(());
+    }
+
+    for entry in fs::read_dir(schedules_dir)? {
+        let path = entry?.path();
+        if path.extension().is_some_and(|ext| ext == "json") {
+            let path_str = path.to_string_lossy();
+            let mut schedule = ClassSchedule::read_file(&path_str)?;
+            for day in &mut schedule.schedule {
+                day.location = None;
+                day.instructor = None;
+            }
+            for extra in &mut schedule.extra_days {
+                extra.location = None;
+                extra.instructor = None;
+            }
+            for date_override in &mut schedule.overrides {
+                date_override.location = None;
+                date_override.instructor = None;
+            }
+            fs::write(&path, serde_json::to_string_pretty(&schedule)? + "\n")?;
+        }
+    }
+
+    Ok(())
+}
+
+fn scrub_personal_config(utils_path: &Path) -> Result<()> {
+    let contents = fs::read_to_string(utils_path)
+        .with_context(|| format!("Failed to read {}", utils_path.display()))?;
+    let scrubbed = contents
+        .replace(DEVICE_MAC, PLACEHOLDER_MAC)
+        .replace(DEVICE_NAME, PLACEHOLDER_DEVICE);
+    fs::write(utils_path, scrubbed)?;
+    Ok(())
+}
+
+pub fn export_template(dest: &str, anonymize: bool) -> Result<()> {
+    let git_root = get_git_root()?;
+    let root = Path::new(&git_root);
+    let dest_path = Path::new(dest);
+
+    if dest_path.exists() && fs::read_dir(dest_path)?.next().is_some() {
+        anyhow::bail!("Destination is not empty: {}", dest);
+    }
+    fs::create_dir_all(dest_path)?;
+
+    // Copy the tool itself without build output or personal data
+    copy_dir(
+        &root.join("journal-automation"),
+        &dest_path.join("journal-automation"),
+        &SKIPPED_DIRS,
+    )?;
+    if anonymize {
+        anonymize_schedules(&dest_path.join("journal-automation/schedules"))?;
+    }
+    scrub_personal_config(&dest_path.join("journal-automation/src/utils.rs"))?;
+
+    // Copy templates and editor configuration
+    for dir in ["templates", ".vscode"] {
+        if root.join(dir).exists() {
+            copy_dir(&root.join(dir), &dest_path.join(dir), &[])?;
+        }
+    }
+    if root.join(".gitignore").exists() {
+        fs::copy(root.join(".gitignore"), dest_path.join(".gitignore"))?;
+    }
+
+    // Start the new repository with an empty journal for this year
+    let dest_str = dest_path.to_string_lossy();
+    create_year_at(&dest_str, Local::now().year() as u32, "journal")?;
+
+    let status = Command::new("git")
+        .arg("init")
+        .current_dir(dest_path)
+        .status()
+        .context("Failed to execute git command")?;
+    if !status.success() {
+        anyhow::bail!("git init failed in {}", dest);
+    }
+
+    println!("Exported journal template to {}", dest);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_copy_dir_skips_data() {
+        let root = std::env::temp_dir().join(format!("ja-export-test-{}", std::process::id()));
+        let from = root.join("journal-automation");
+        let to = root.join("export");
+        for (file, contents) in [
+            ("src/main.rs", "fn main() {}"),
+            ("schedules/cs101.json", "{}"),
+            ("runs/runs.csv", "start,distance_m"),
+            ("videos/history.csv", "date,video"),
+            ("videos/list.txt", "video"),
+            ("paper/volumes.json", "[]"),
+            ("target/debug/ja", ""),
+        ] {
+            let path = from.join(file);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, contents).unwrap();
+        }
+
+        copy_dir(&from, &to, &SKIPPED_DIRS).unwrap();
+        let mut copied: Vec<String> = walkdir::WalkDir::new(&to)
+            .into_iter()
+            .filter_map(|e| e.ok())
+            .filter(|e| e.file_type().is_file())
+            .map(|e| {
+                let relative = e.path().strip_prefix(&to).unwrap();
+                relative.to_string_lossy().replace('\\', "/")
+            })
+            .collect();
+        copied.sort();
+        fs::remove_dir_all(&root).unwrap();
+
+        assert_eq!(copied, vec!["schedules/cs101.json", "src/main.rs"]);
+    }
+}
@@ -0,0 +1,476 @@
+use crate::chart;
+use crate::index::EntryInfo;
+use crate::journal::indexed_journal_files;
+use crate::notebook::{month_folder, JournalFile};
+use crate::runs::{load_runs, Run};
+use crate::stats::streaks;
+use crate::utils::get_git_root;
+use anyhow::{Context, Result};
+use chrono::{Datelike, Months, NaiveDate};
+use std::{
+    collections::BTreeMap,
+    fs,
+    io::ErrorKind,
+    path::{Path, PathBuf},
+};
+
+const GOAL_TAG_PREFIX: &str = "goal/";
+
+/// A checklist item in a month's goals file.
+#[derive(Debug, PartialEq)]
+pub struct Goal {
+    pub line: usize,
+    pub text: String,
+    pub done: bool,
+}
+
+/// A target found in a goal's text, such as "run 50 miles" or "journal 25 days".
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Metric {
+    Miles(f64),
+    Runs(f64),
+    Days(f64),
+    Streak(f64),
+    Words(f64),
+}
+
+// The verbs and units each metric is written with. Articles are ignored, so "keep a 10-day
+// streak" reads as "keep", 10, "day streak".
+type Phrasing = (
+    &'static [&'static str],
+    &'static [&'static str],
+    fn(f64) -> Metric,
+);
+const PHRASINGS: [Phrasing; 6] = [
+    (&["run", "jog"], &["mile", "miles", "mi"], Metric::Miles),
+    (&["go for", "log"], &["run", "runs"], Metric::Runs),
+    (&["run"], &["times"], Metric::Runs),
+    (
+        &["journal", "write"],
+        &["day", "days", "entries"],
+        Metric::Days,
+    ),
+    (
+        &["", "keep", "reach", "hit"],
+        &["day streak"],
+        Metric::Streak,
+    ),
+    (&["journal", "write"], &["word", "words"], Metric::Words),
+];
+
+impl Metric {
+    /// Reads goals written as a verb, a number and a unit, optionally followed by "this month".
+    /// Anything else (including "run 5 days a week") is checked off by hand or with a
+    /// `#goal/...` tag rather than guessed at.
+    pub fn parse(text: &str) -> Option<Metric> {
+        let lower = text.to_lowercase();
+        let words: Vec<&str> = lower
+            .split(|c: char| c.is_whitespace() || matches!(c, '-' | ','))
+            .map(|w| w.trim_end_matches(['.', '!']))
+            .filter(|w| !w.is_empty() && !matches!(*w, "a" | "an"))
+            .collect();
+        let i = words.iter().position(|w| w.parse::<f64>().is_ok())?;
+        let target = words[i].parse::<f64>().ok()?;
+        let verb = words[..i].join(" ");
+        let unit = words[i + 1..].join(" ");
+        let unit = unit.strip_suffix(" this month").unwrap_or(&unit);
+
+        PHRASINGS
+            .iter()
+            .find(|(verbs, units, _)| verbs.contains(&verb.as_str()) && units.contains(&unit))
+            .map(|(_, _, metric)| metric(target))
+    }
+
+    pub fn target(&self) -> f64 {
+        match *self {
+            Metric::Miles(t)
+            | Metric::Runs(t)
+            | Metric::Days(t)
+            | Metric::Streak(t)
+            | Metric::Words(t) => t,
+        }
+    }
+
+    pub fn unit(&self) -> &'static str {
+        match self {
+            Metric::Miles(_) => "miles",
+            Metric::Runs(_) => "runs",
+            Metric::Days(_) => "days",
+            Metric::Streak(_) => "day streak",
+            Metric::Words(_) => "words",
+        }
+    }
+
+    pub fn current(&self, month: &MonthData) -> f64 {
+        match self {
+            Metric::Miles(_) => month.miles,
+            Metric::Runs(_) => month.runs as f64,
+            Metric::Days(_) => month.days_written as f64,
+            Metric::Streak(_) => month.longest_streak as f64,
+            Metric::Words(_) => month.words as f64,
+        }
+    }
+}
+
+/// What the metrics are measured against for one month.
+#[derive(Debug, Default)]
+pub struct MonthData {
+    pub days_written: u32,
+    pub longest_streak: i64,
+    pub words: u64,
+    pub runs: u32,
+    pub miles: f64,
+    pub references: BTreeMap<String, NaiveDate>, // goal slug -> first entry tagging it
+}
+
+/// `Run a 10k!` becomes `run-a-10k`, which entries reference as `#goal/run-a-10k`.
+pub fn slug(text: &str) -> String {
+    text.to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+pub fn parse_goals(contents: &str) -> Vec<Goal> {
+    contents
+        .lines()
+        .enumerate()
+        .filter_map(|(line, text)| {
+            let item = text.trim_start().strip_prefix(['-', '*'])?.trim_start();
+            let (done, text) = if let Some(text) = item.strip_prefix("[ ]") {
+                (false, text)
+            } else {
+                (true, item.strip_prefix("[x]").or(item.strip_prefix("[X]"))?)
+            };
+            Some(Goal {
+                line,
+                text: text.trim().to_string(),
+                done,
+            })
+        })
+        .collect()
+}
+
+/// Checks off the given goals, leaving every other line as it was.
+pub fn check_off(contents: &str, goals: &[&Goal]) -> String {
+    let mut lines: Vec<String> = contents.lines().map(str::to_string).collect();
+    for goal in goals {
+        if let Some(line) = lines.get_mut(goal.line) {
+            *line = line.replacen("[ ]", "[x]", 1);
+        }
+    }
+    let mut updated = lines.join("\n");
+    if contents.ends_with('\n') {
+        updated.push('\n');
+    }
+    updated
+}
+
+/// Appends goals that aren't already in the file (by slug), returning the new contents and how many
+/// were added.
+fn roll_into(contents: &str, goals: &[&Goal]) -> (String, usize) {
+    let existing: Vec<String> = parse_goals(contents)
+        .iter()
+        .map(|g| slug(&g.text))
+        .collect();
+    let mut updated = contents.trim_end_matches('\n').to_string();
+    let mut added = 0;
+    for goal in goals {
+        if existing.contains(&slug(&goal.text)) {
+            continue;
+        }
+        if !updated.is_empty() {
+            updated.push('\n');
+        }
+        updated.push_str(&format!("- [ ] {}", goal.text));
+        added += 1;
+    }
+    updated.push('\n');
+    (updated, added)
+}
+
+/// `{Month} goals.md` in the month's journal folder.
+pub fn goals_path(month: NaiveDate) -> Result<PathBuf> {
+    Ok(Path::new(&get_git_root()?)
+        .join("journal")
+        .join(month_folder(month))
+        .join(format!("{} goals.md", month.format("%B"))))
+}
+
+/// A goals file's contents, empty when the month has none yet.
+fn read_goals(path: &Path) -> Result<String> {
+    match fs::read_to_string(path) {
+        Ok(contents) => Ok(contents),
+        Err(e) if e.kind() == ErrorKind::NotFound => Ok(String::new()),
+        Err(e) => Err(e).with_context(|| format!("Failed to read {}", path.display())),
+    }
+}
+
+fn month_data(month: NaiveDate, entries: &[(JournalFile, EntryInfo)], runs: &[Run]) -> MonthData {
+    let in_month = |date: NaiveDate| date.year() == month.year() && date.month() == month.month();
+    let mut data = MonthData::default();
+
+    let mut dates = Vec::new();
+    for (file, info) in entries {
+        let Some(date) = file.date().filter(|d| in_month(*d)) else {
+            continue;
+        };
+        if info.size > 0 {
+            data.days_written += 1;
+            data.words += info.words;
+            dates.push(date);
+        }
+        for tag in &info.tags {
+            if let Some(goal) = tag.strip_prefix(GOAL_TAG_PREFIX) {
+                data.references.entry(slug(goal)).or_insert(date);
+            }
+        }
+    }
+    dates.sort();
+    data.longest_streak = streaks(&dates)
+        .iter()
+        .map(|(first, last)| (*last - *first).num_days() + 1)
+        .max()
+        .unwrap_or(0);
+
+    for run in runs.iter().filter(|r| in_month(r.date())) {
+        data.runs += 1;
+        data.miles += run.miles();
+    }
+    data
+}
+
+/// Whether a goal is met, and when it was tagged if it was.
+fn evaluate(goal: &Goal, data: &MonthData) -> (bool, Option<NaiveDate>) {
+    let referenced = data.references.get(&slug(&goal.text)).copied();
+    let reached = Metric::parse(&goal.text).is_some_and(|m| m.current(data) >= m.target());
+    (goal.done || reached || referenced.is_some(), referenced)
+}
+
+/// Shows progress on a month's goals and checks off the ones that are now met.
+pub fn month_goals(month: NaiveDate) -> Result<()> {
+    let path = goals_path(month)?;
+    let contents = read_goals(&path)?;
+    let goals = parse_goals(&contents);
+    if goals.is_empty() {
+        println!("No goals in {}", path.display());
+        return Ok(());
+    }
+    let data = month_data(month, &indexed_journal_files("journal")?, &load_runs()?);
+
+    println!("\n{} Goals", month.format("%B %Y"));
+    println!("===================================");
+    let text_width = goals
+        .iter()
+        .map(|g| g.text.chars().count())
+        .max()
+        .unwrap_or(0);
+    let mut done = 0;
+    let mut newly_done = Vec::new();
+    for goal in &goals {
+        let (met, referenced) = evaluate(goal, &data);
+        if met {
+            done += 1;
+            if !goal.done {
+                newly_done.push(goal);
+            }
+        }
+        let marker = if met { "✓" } else { " " };
+        let detail = match (Metric::parse(&goal.text), referenced) {
+            (Some(metric), _) => {
+                let current = metric.current(&data);
+                let rate = (current / metric.target() * 100.0).min(100.0);
+                format!(
+                    "{:.0}/{:.0} {} | {:>5.1}% {}",
+                    current,
+                    metric.target(),
+                    metric.unit(),
+                    rate,
+                    chart::bar(rate, 20)
+                )
+            }
+            (None, Some(date)) => format!("tagged {}", date.format("%b %d")),
+            (None, None) if !met => format!("tag an entry #goal/{}", slug(&goal.text)),
+            (None, None) => String::new(),
+        };
+        let line = format!(
+            "{} {:<text_width$} | {}",
+            marker,
+            goal.text,
+            detail,
+            text_width = text_width
+        );
+        println!("{}", line.trim_end());
+    }
+
+    let rate = done as f64 / goals.len() as f64 * 100.0;
+    println!(
+        "\n{}/{} goals complete | {:>5.1}% {}",
+        done,
+        goals.len(),
+        rate,
+        chart::bar(rate, 20)
+    );
+    if !newly_done.is_empty() {
+        fs::write(&path, check_off(&contents, &newly_done))
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+        println!("Checked off {} in {}", newly_done.len(), path.display());
+    }
+    Ok(())
+}
+
+/// One line per month of the year with goals met out of goals set.
+pub fn year_goals(year: i32) -> Result<()> {
+    let entries = indexed_journal_files("journal")?;
+    let runs = load_runs()?;
+    println!("\n{} Goals", year);
+    println!("===================================");
+
+    let (mut total_done, mut total) = (0, 0);
+    for month in 1..=12 {
+        let Some(month) = NaiveDate::from_ymd_opt(year, month, 1) else {
+            continue;
+        };
+        let contents = read_goals(&goals_path(month)?)?;
+        let goals = parse_goals(&contents);
+        if goals.is_empty() {
+            continue;
+        }
+        let data = month_data(month, &entries, &runs);
+        let done = goals.iter().filter(|g| evaluate(g, &data).0).count();
+        let rate = done as f64 / goals.len() as f64 * 100.0;
+        let marker = if done == goals.len() { " ✓" } else { "" };
+        println!(
+            "{:<9} | {:>2}/{:<2} | {:>5.1}% {}{}",
+            month.format("%B"),
+            done,
+            goals.len(),
+            rate,
+            chart::bar(rate, 20),
+            marker
+        );
+        total_done += done;
+        total += goals.len();
+    }
+
+    if total == 0 {
+        println!("No goals set in {}", year);
+    } else {
+        println!(
+            "\n{}/{} goals complete ({:.1}%)",
+            total_done,
+            total,
+            total_done as f64 / total as f64 * 100.0
+        );
+    }
+    Ok(())
+}
+
+/// Copies the month's unfinished goals into next month's goals file.
+pub fn rollover_goals(month: NaiveDate) -> Result<()> {
+    let path = goals_path(month)?;
+    let contents = read_goals(&path)?;
+    let goals = parse_goals(&contents);
+    let data = month_data(month, &indexed_journal_files("journal")?, &load_runs()?);
+    let unfinished: Vec<&Goal> = goals.iter().filter(|g| !evaluate(g, &data).0).collect();
+    if unfinished.is_empty() {
+        println!("No unfinished goals in {}", month.format("%B %Y"));
+        return Ok(());
+    }
+
+    let next = month + Months::new(1);
+    let next_path = goals_path(next)?;
+    if let Some(parent) = next_path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let next_contents = read_goals(&next_path)?;
+    let (updated, added) = roll_into(&next_contents, &unfinished);
+    if added > 0 {
+        fs::write(&next_path, updated)
+            .with_context(|| format!("Failed to write {}", next_path.display()))?;
+    }
+    println!(
+        "Rolled {} unfinished goals into {} ({} already there)",
+        added,
+        next_path.display(),
+        unfinished.len() - added
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_goals_are_checked_off() {
+        let contents = "# March goals\n\n- [ ] Run a 10k!\n- [x] Read a book\n* [ ] Call grandma\n";
+        let goals = parse_goals(contents);
+        assert_eq!(goals.len(), 3);
+        assert_eq!(slug(&goals[0].text), "run-a-10k");
+        assert!(goals[1].done);
+
+        let updated = check_off(contents, &[&goals[0]]);
+        assert_eq!(
+            updated,
+            "# March goals\n\n- [x] Run a 10k!\n- [x] Read a book\n* [ ] Call grandma\n"
+        );
+    }
+
+    #[test]
+    fn test_metric_parse() {
+        assert_eq!(Metric::parse("Run 50 miles"), Some(Metric::Miles(50.0)));
+        assert_eq!(Metric::parse("run 12.5 mi!"), Some(Metric::Miles(12.5)));
+        assert_eq!(Metric::parse("Go for 8 runs"), Some(Metric::Runs(8.0)));
+        assert_eq!(Metric::parse("Run 12 times"), Some(Metric::Runs(12.0)));
+        assert_eq!(Metric::parse("Journal 25 days"), Some(Metric::Days(25.0)));
+        assert_eq!(Metric::parse("a 10-day streak"), Some(Metric::Streak(10.0)));
+        assert_eq!(
+            Metric::parse("write 10000 words"),
+            Some(Metric::Words(10000.0))
+        );
+        assert_eq!(
+            Metric::parse("Run 50 miles this month"),
+            Some(Metric::Miles(50.0))
+        );
+        assert_eq!(
+            Metric::parse("Keep a 30 day streak"),
+            Some(Metric::Streak(30.0))
+        );
+        assert_eq!(Metric::parse("Run a 10k"), None);
+        assert_eq!(Metric::parse("Run 5 days a week"), None);
+        assert_eq!(Metric::parse("Journal 5 days a week"), None);
+        assert_eq!(Metric::parse("Read 2 books"), None);
+    }
+
+    #[test]
+    fn test_evaluate_and_roll_over() {
+        let data = MonthData {
+            days_written: 20,
+            miles: 52.0,
+            references: BTreeMap::from([(
+                "call-grandma".to_string(),
+                NaiveDate::from_ymd_opt(2024, 3, 5).unwrap(),
+            )]),
+            ..Default::default()
+        };
+        let contents =
+            "- [ ] Run 50 miles\n- [ ] Journal 25 days\n- [ ] Call grandma\n- [ ] Read 2 books\n";
+        let goals = parse_goals(contents);
+        let met: Vec<bool> = goals.iter().map(|g| evaluate(g, &data).0).collect();
+        assert_eq!(met, vec![true, false, true, false]);
+
+        let unfinished: Vec<&Goal> = goals.iter().filter(|g| !evaluate(g, &data).0).collect();
+        let (updated, added) = roll_into("# April goals\n\n- [ ] Read 2 books\n", &unfinished);
+        assert_eq!(added, 1);
+        assert_eq!(
+            updated,
+            "# April goals\n\n- [ ] Read 2 books\n- [ ] Journal 25 days\n"
+        );
+        assert_eq!(
+            roll_into("", &unfinished).0,
+            "- [ ] Journal 25 days\n- [ ] Read 2 books\n"
+        );
+    }
+}
@@ -0,0 +1,109 @@
+use std::collections::BTreeMap;
+
+// Written when a header value is skipped or unknown
+pub const MISSING: &str = "—";
+
+fn cells(line: &str) -> Option<Vec<String>> {
+    let inner = line.trim().strip_prefix('|')?.strip_suffix('|')?;
+    Some(inner.split('|').map(|c| c.trim().to_string()).collect())
+}
+
+fn is_separator(line: &str) -> bool {
+    cells(line).is_some_and(|cells| {
+        cells
+            .iter()
+            .all(|c| !c.is_empty() && c.chars().all(|ch| matches!(ch, '-' | ':')))
+    })
+}
+
+/// The first row of every table in an entry by column name, e.g. `weather` from the header table or
+/// `sleep` from the health table. Columns seen earlier win, and skipped (`—`) cells are left out.
+pub fn table_fields(contents: &str) -> BTreeMap<String, String> {
+    let lines: Vec<&str> = contents.lines().collect();
+    let mut fields = BTreeMap::new();
+    for window in lines.windows(3) {
+        let [header, separator, row] = window else {
+            continue;
+        };
+        if !is_separator(separator) {
+            continue;
+        }
+        let (Some(names), Some(values)) = (cells(header), cells(row)) else {
+            continue;
+        };
+        for (name, value) in names.into_iter().zip(values) {
+            if !name.is_empty() && !value.is_empty() && value != MISSING {
+                fields.entry(name.to_lowercase()).or_insert(value);
+            }
+        }
+    }
+    fields
+}
+
+/// Sets a column in the entry's header table (the one with a `device` column), adding the column
+/// to entries written before it existed. `None` if the entry has no header table.
+pub fn set_header_field(contents: &str, column: &str, value: &str) -> Option<String> {
+    let mut lines: Vec<String> = contents.lines().map(str::to_string).collect();
+    let start = (0..lines.len().saturating_sub(2)).find(|&i| {
+        cells(&lines[i]).is_some_and(|names| names.iter().any(|n| n == "device"))
+            && is_separator(&lines[i + 1])
+            && cells(&lines[i + 2]).is_some()
+    })?;
+
+    let names = cells(&lines[start])?;
+    let mut row = cells(&lines[start + 2])?;
+    match names.iter().position(|n| n == column) {
+        Some(index) => {
+            row.resize(names.len(), MISSING.to_string());
+            row[index] = value.to_string();
+        }
+        None => {
+            lines[start] = format!("{} {} |", lines[start].trim_end(), column);
+            lines[start + 1] = format!(
+                "{} {} |",
+                lines[start + 1].trim_end(),
+                "-".repeat(column.len())
+            );
+            row.push(value.to_string());
+        }
+    }
+    lines[start + 2] = format!("| {} |", row.join(" | "));
+
+    let mut updated = lines.join("\n");
+    if contents.ends_with('\n') {
+        updated.push('\n');
+    }
+    Some(updated)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ENTRY: &str = "# Friday, March 15, 2024\n\n| device  | location     | weather    |\n| ------- | ------------ | ---------- |\n| ✨ luna | Illinois, US | 40-55 F ☀️ |\n\nText | with a pipe\n\n<!-- ja:health:start -->\n| sleep | steps | resting hr | workouts |\n| ----- | ----- | ---------- | -------- |\n| 7h 05m | 8000 | — | — |\n<!-- ja:health:end -->\n";
+
+    #[test]
+    fn test_table_fields() {
+        let fields = table_fields(ENTRY);
+        assert_eq!(fields["weather"], "40-55 F ☀️");
+        assert_eq!(fields["sleep"], "7h 05m");
+        assert_eq!(fields["steps"], "8000");
+        assert!(!fields.contains_key("resting hr"));
+        assert_eq!(fields.len(), 5);
+    }
+
+    #[test]
+    fn test_set_header_field() {
+        let with_mood = set_header_field(ENTRY, "mood", "4").unwrap();
+        assert!(with_mood.contains(
+            "| device  | location     | weather    | mood |\n| ------- | ------------ | ---------- | ---- |\n| ✨ luna | Illinois, US | 40-55 F ☀️ | 4 |\n"
+        ));
+        assert!(with_mood.ends_with("<!-- ja:health:end -->\n"));
+
+        let changed = set_header_field(&with_mood, "mood", "2").unwrap();
+        assert!(changed.contains("| ✨ luna | Illinois, US | 40-55 F ☀️ | 2 |\n"));
+        assert_eq!(table_fields(&changed)["mood"], "2");
+
+        assert!(set_header_field("# Class notes\n", "mood", "4").is_none());
+    }
+}
@@ -0,0 +1,283 @@
+use crate::block::write_block;
+use crate::journal::get_journal_path_for_date;
+use anyhow::{Context, Result};
+use chrono::{DateTime, FixedOffset, NaiveDate};
+use quick_xml::{events::BytesStart, events::Event, Reader};
+use std::{
+    collections::{BTreeMap, HashMap},
+    fs::{self, File},
+    io::{BufRead, BufReader, ErrorKind},
+};
+
+const SLEEP_TYPE: &str = "HKCategoryTypeIdentifierSleepAnalysis";
+const STEPS_TYPE: &str = "HKQuantityTypeIdentifierStepCount";
+const RESTING_HR_TYPE: &str = "HKQuantityTypeIdentifierRestingHeartRate";
+
+#[derive(Debug, Default)]
+struct DayMetrics {
+    sleep: Vec<(DateTime<FixedOffset>, DateTime<FixedOffset>)>,
+    steps_by_source: HashMap<String, f64>,
+    resting_hr: Vec<f64>,
+    workouts: Vec<(String, f64)>, // (activity, minutes)
+}
+
+impl DayMetrics {
+    /// Minutes asleep, merging overlapping samples recorded by different devices.
+    fn sleep_minutes(&self) -> i64 {
+        let mut intervals = self.sleep.clone();
+        intervals.sort();
+
+        let mut total = 0;
+        let mut current: Option<(DateTime<FixedOffset>, DateTime<FixedOffset>)> = None;
+        for (start, end) in intervals {
+            match current {
+                Some((cur_start, cur_end)) if start <= cur_end => {
+                    current = Some((cur_start, cur_end.max(end)));
+                }
+                _ => {
+                    if let Some((cur_start, cur_end)) = current {
+                        total += (cur_end - cur_start).num_minutes();
+                    }
+                    current = Some((start, end));
+                }
+            }
+        }
+        if let Some((cur_start, cur_end)) = current {
+            total += (cur_end - cur_start).num_minutes();
+        }
+
+        total
+    }
+
+    /// Steps for the day. Phone and watch both count steps, so the busiest source is used
+    /// rather than the sum.
+    fn steps(&self) -> Option<f64> {
+        self.steps_by_source.values().copied().reduce(f64::max)
+    }
+
+    fn resting_hr(&self) -> Option<f64> {
+        if self.resting_hr.is_empty() {
+            None
+        } else {
+            Some(self.resting_hr.iter().sum::<f64>() / self.resting_hr.len() as f64)
+        }
+    }
+
+    fn to_table(&self) -> String {
+        let sleep = match self.sleep_minutes() {
+            0 => "—".to_string(),
+            minutes => format!("{}h {:02}m", minutes / 60, minutes % 60),
+        };
+        let steps = self
+            .steps()
+            .map(|s| format!("{:.0}", s))
+            .unwrap_or_else(|| "—".to_string());
+        let resting_hr = self
+            .resting_hr()
+            .map(|hr| format!("{:.0} bpm", hr))
+            .unwrap_or_else(|| "—".to_string());
+        let workouts = if self.workouts.is_empty() {
+            "—".to_string()
+        } else {
+            self.workouts
+                .iter()
+                .map(|(activity, minutes)| format!("{} {:.0} min", activity, minutes))
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+
+        format!(
+            "| sleep | steps | resting hr | workouts |\n| ----- | ----- | ---------- | -------- |\n| {} | {} | {} | {} |",
+            sleep, steps, resting_hr, workouts
+        )
+    }
+}
+
+fn attribute(element: &BytesStart, name: &str) -> Result<Option<String>> {
+    Ok(match element.try_get_attribute(name)? {
+        Some(attr) => Some(attr.unescape_value()?.into_owned()),
+        None => None,
+    })
+}
+
+fn parse_timestamp(value: &str) -> Result<DateTime<FixedOffset>> {
+    DateTime::parse_from_str(value, "%Y-%m-%d %H:%M:%S %z")
+        .with_context(|| format!("Invalid timestamp in health export: {}", value))
+}
+
+/// Turns `HKWorkoutActivityTypeTraditionalStrengthTraining` into `Traditional Strength Training`.
+fn activity_name(activity_type: &str) -> String {
+    let name = activity_type.trim_start_matches("HKWorkoutActivityType");
+    let mut words = String::new();
+    for (i, c) in name.chars().enumerate() {
+        if i > 0 && c.is_uppercase() {
+            words.push(' ');
+        }
+        words.push(c);
+    }
+    words
+}
+
+fn record_element(element: &BytesStart, days: &mut BTreeMap<NaiveDate, DayMetrics>) -> Result<()> {
+    let record_type = attribute(element, "type")?.unwrap_or_default();
+    if record_type != SLEEP_TYPE && record_type != STEPS_TYPE && record_type != RESTING_HR_TYPE {
+        return Ok(());
+    }
+
+    let start = match attribute(element, "startDate")? {
+        Some(value) => parse_timestamp(&value)?,
+        None => return Ok(()),
+    };
+    let end = match attribute(element, "endDate")? {
+        Some(value) => parse_timestamp(&value)?,
+        None => start,
+    };
+    let value = attribute(element, "value")?.unwrap_or_default();
+
+    if record_type == SLEEP_TYPE {
+        // Only count time asleep, not time in bed or awake. Sleep belongs to the day you wake up.
+        if value.starts_with("HKCategoryValueSleepAnalysisAsleep") {
+            days.entry(end.date_naive())
+                .or_default()
+                .sleep
+                .push((start, end));
+        }
+    } else if let Ok(number) = value.parse::<f64>() {
+        let day = days.entry(start.date_naive()).or_default();
+        if record_type == STEPS_TYPE {
+            let source = attribute(element, "sourceName")?.unwrap_or_default();
+            *day.steps_by_source.entry(source).or_default() += number;
+        } else {
+            day.resting_hr.push(number);
+        }
+    }
+
+    Ok(())
+}
+
+fn workout_element(element: &BytesStart, days: &mut BTreeMap<NaiveDate, DayMetrics>) -> Result<()> {
+    let activity = attribute(element, "workoutActivityType")?.unwrap_or_default();
+    let start = match attribute(element, "startDate")? {
+        Some(value) => parse_timestamp(&value)?,
+        None => return Ok(()),
+    };
+
+    let mut minutes = attribute(element, "duration")?
+        .and_then(|d| d.parse::<f64>().ok())
+        .unwrap_or(0.0);
+    match attribute(element, "durationUnit")?.as_deref() {
+        Some("s") | Some("sec") => minutes /= 60.0,
+        Some("hr") => minutes *= 60.0,
+        _ => {}
+    }
+
+    days.entry(start.date_naive())
+        .or_default()
+        .workouts
+        .push((activity_name(&activity), minutes));
+    Ok(())
+}
+
+/// Streams the export so multi-gigabyte files never have to fit in memory.
+fn parse_export<R: BufRead>(source: R) -> Result<BTreeMap<NaiveDate, DayMetrics>> {
+    let mut reader = Reader::from_reader(source);
+    let mut buf = Vec::new();
+    let mut days: BTreeMap<NaiveDate, DayMetrics> = BTreeMap::new();
+
+    loop {
+        match reader.read_event_into(&mut buf)? {
+            Event::Eof => break,
+            Event::Start(element) | Event::Empty(element) => match element.name().as_ref() {
+                b"Record" => record_element(&element, &mut days)?,
+                b"Workout" => workout_element(&element, &mut days)?,
+                _ => {}
+            },
+            _ => {}
+        }
+        buf.clear();
+    }
+
+    Ok(days)
+}
+
+pub fn import_health(export_path: &str) -> Result<()> {
+    let file =
+        File::open(export_path).with_context(|| format!("Failed to open {}", export_path))?;
+
+    // Accept either the zip the Health app shares or an already extracted export.xml
+    let days = if export_path.ends_with(".xml") {
+        parse_export(BufReader::new(file))?
+    } else {
+        let mut archive = zip::ZipArchive::new(file)
+            .with_context(|| format!("Failed to read zip archive: {}", export_path))?;
+        let xml_name = archive
+            .file_names()
+            .find(|name| name.ends_with("/export.xml") || *name == "export.xml")
+            .map(|name| name.to_string())
+            .ok_or_else(|| anyhow::anyhow!("No export.xml found in {}", export_path))?;
+        let xml = archive.by_name(&xml_name)?;
+        parse_export(BufReader::new(xml))?
+    };
+
+    let mut updated = 0;
+    let mut skipped = 0;
+    for (date, metrics) in &days {
+        let journal_path = get_journal_path_for_date(*date, "journal")?;
+        // Days without an entry are left alone
+        let contents = match fs::read_to_string(&journal_path) {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == ErrorKind::NotFound => continue,
+            Err(e) => return Err(e).with_context(|| format!("Failed to read {}", journal_path)),
+        };
+
+        // Don't turn an empty placeholder into a "completed" day
+        if contents.trim().is_empty() {
+            skipped += 1;
+            continue;
+        }
+
+        if write_block(&journal_path, "health", &metrics.to_table())? {
+            updated += 1;
+        }
+    }
+
+    println!(
+        "Imported health data for {} days ({} entries updated, {} empty entries skipped)",
+        days.len(),
+        updated,
+        skipped
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_export() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<HealthData locale="en_US">
+ <Record type="HKQuantityTypeIdentifierStepCount" sourceName="Phone" unit="count" startDate="2024-03-15 08:00:00 -0500" endDate="2024-03-15 08:10:00 -0500" value="1200"/>
+ <Record type="HKQuantityTypeIdentifierStepCount" sourceName="Watch" unit="count" startDate="2024-03-15 08:00:00 -0500" endDate="2024-03-15 08:10:00 -0500" value="1500"/>
+ <Record type="HKQuantityTypeIdentifierRestingHeartRate" sourceName="Watch" unit="count/min" startDate="2024-03-15 09:00:00 -0500" endDate="2024-03-15 09:00:00 -0500" value="58"/>
+ <Record type="HKCategoryTypeIdentifierSleepAnalysis" sourceName="Watch" startDate="2024-03-14 23:00:00 -0500" endDate="2024-03-15 03:00:00 -0500" value="HKCategoryValueSleepAnalysisAsleepCore"/>
+ <Record type="HKCategoryTypeIdentifierSleepAnalysis" sourceName="Phone" startDate="2024-03-15 02:00:00 -0500" endDate="2024-03-15 06:30:00 -0500" value="HKCategoryValueSleepAnalysisAsleepUnspecified">
+  <MetadataEntry key="HKTimeZone" value="America/Chicago"/>
+ </Record>
+ <Record type="HKCategoryTypeIdentifierSleepAnalysis" sourceName="Phone" startDate="2024-03-14 22:30:00 -0500" endDate="2024-03-15 06:45:00 -0500" value="HKCategoryValueSleepAnalysisInBed"/>
+ <Workout workoutActivityType="HKWorkoutActivityTypeRunning" duration="32.5" durationUnit="min" startDate="2024-03-15 17:00:00 -0500" endDate="2024-03-15 17:32:30 -0500"/>
+</HealthData>"#;
+
+        let days = parse_export(xml.as_bytes()).unwrap();
+        let day = &days[&NaiveDate::from_ymd_opt(2024, 3, 15).unwrap()];
+
+        assert_eq!(day.sleep_minutes(), 7 * 60 + 30);
+        assert_eq!(day.steps(), Some(1500.0));
+        assert_eq!(day.resting_hr(), Some(58.0));
+        assert_eq!(day.workouts, vec![("Running".to_string(), 32.5)]);
+        assert!(day
+            .to_table()
+            .ends_with("| 7h 30m | 1500 | 58 bpm | Running 32 min |"));
+    }
+}
@@ -0,0 +1,874 @@
+use crate::journal::process_journal_files;
+use crate::schedule::{
+    get_schedule_path, week_number, ClassDay, ClassSchedule, Exclusion, Session, Weekday,
+};
+use anyhow::{Context, Result};
+use chrono::{DateTime, Datelike, Duration, Local, NaiveDate, NaiveDateTime, NaiveTime, Utc};
+use std::{collections::HashMap, fs, path::Path};
+
+/// A single `NAME;PARAM=VALUE:value` content line.
+#[derive(Debug, Clone)]
+struct Property {
+    name: String,
+    params: HashMap<String, String>,
+    value: String,
+}
+
+/// The properties of one VEVENT, keyed by name. Some properties (like EXDATE) can repeat.
+#[derive(Debug, Default)]
+struct Event {
+    properties: Vec<Property>,
+}
+
+impl Event {
+    fn get(&self, name: &str) -> Option<&Property> {
+        self.properties.iter().find(|p| p.name == name)
+    }
+
+    fn get_all<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a Property> {
+        self.properties.iter().filter(move |p| p.name == name)
+    }
+}
+
+fn unescape_text(value: &str) -> String {
+    value
+        .replace("\\n", "\n")
+        .replace("\\N", "\n")
+        .replace("\\,", ",")
+        .replace("\\;", ";")
+        .replace("\\\\", "\\")
+}
+
+fn parse_property(line: &str) -> Option<Property> {
+    // The value starts after the first colon that isn't inside a quoted parameter
+    let mut in_quotes = false;
+    let split = line.char_indices().find(|&(_, c)| {
+        if c == '"' {
+            in_quotes = !in_quotes;
+        }
+        c == ':' && !in_quotes
+    })?;
+    let (head, value) = (&line[..split.0], &line[split.0 + 1..]);
+
+    let mut parts = head.split(';');
+    let name = parts.next()?.to_uppercase();
+    let params = parts
+        .filter_map(|p| p.split_once('='))
+        .map(|(k, v)| (k.to_uppercase(), v.trim_matches('"').to_string()))
+        .collect();
+
+    Some(Property {
+        name,
+        params,
+        value: value.to_string(),
+    })
+}
+
+/// Parses every VEVENT in an iCalendar file, unfolding continuation lines first.
+fn parse_events(contents: &str) -> Vec<Event> {
+    let mut lines: Vec<String> = Vec::new();
+    for line in contents.lines() {
+        let line = line.trim_end_matches('\r');
+        if let Some(rest) = line.strip_prefix([' ', '\t']) {
+            if let Some(last) = lines.last_mut() {
+                last.push_str(rest);
+                continue;
+            }
+        }
+        lines.push(line.to_string());
+    }
+
+    let mut events = Vec::new();
+    let mut current: Option<Event> = None;
+    for line in &lines {
+        match line.as_str() {
+            "BEGIN:VEVENT" => current = Some(Event::default()),
+            "END:VEVENT" => {
+                if let Some(event) = current.take() {
+                    events.push(event);
+                }
+            }
+            _ => {
+                if let Some(event) = current.as_mut() {
+                    if let Some(property) = parse_property(line) {
+                        event.properties.push(property);
+                    }
+                }
+            }
+        }
+    }
+
+    events
+}
+
+/// Parses a DATE or DATE-TIME value into local wall-clock time. UTC values (ending in `Z`)
+/// are converted to the local time zone; values with a TZID are taken as written.
+fn parse_datetime(value: &str) -> Result<NaiveDateTime> {
+    if let Some(utc) = value.strip_suffix('Z') {
+        let naive = NaiveDateTime::parse_from_str(utc, "%Y%m%dT%H%M%S")
+            .with_context(|| format!("Invalid date-time in calendar: {}", value))?;
+        return Ok(DateTime::<Utc>::from_naive_utc_and_offset(naive, Utc)
+            .with_timezone(&Local)
+            .naive_local());
+    }
+
+    if value.len() == 8 {
+        let date = NaiveDate::parse_from_str(value, "%Y%m%d")
+            .with_context(|| format!("Invalid date in calendar: {}", value))?;
+        return date
+            .and_hms_opt(0, 0, 0)
+            .ok_or_else(|| anyhow::anyhow!("Invalid date in calendar: {}", value));
+    }
+
+    NaiveDateTime::parse_from_str(value, "%Y%m%dT%H%M%S")
+        .with_context(|| format!("Invalid date-time in calendar: {}", value))
+}
+
+fn weekday_from_ics(code: &str) -> Option<Weekday> {
+    // BYDAY entries may carry an ordinal prefix (e.g. "1MO"), which weekly rules don't use
+    match code.trim_start_matches(|c: char| c.is_ascii_digit() || c == '-' || c == '+') {
+        "MO" => Some(Weekday::Monday),
+        "TU" => Some(Weekday::Tuesday),
+        "WE" => Some(Weekday::Wednesday),
+        "TH" => Some(Weekday::Thursday),
+        "FR" => Some(Weekday::Friday),
+        "SA" => Some(Weekday::Saturday),
+        "SU" => Some(Weekday::Sunday),
+        _ => None,
+    }
+}
+
+// Meeting types recognised in an event summary, with the abbreviations registrars use
+const SECTIONS: [(&str, &[&str]); 7] = [
+    ("Lecture", &["lecture", "lec"]),
+    ("Lab", &["lab", "laboratory"]),
+    ("Discussion", &["discussion", "dis", "disc"]),
+    ("Recitation", &["recitation", "rec"]),
+    ("Seminar", &["seminar", "sem"]),
+    ("Tutorial", &["tutorial", "tut"]),
+    ("Studio", &["studio"]),
+];
+
+/// The section named in a summary such as "CS201 Lab" or "CS 201 LEC", if any. Other words (as in
+/// "Intro to CS") are part of the title, not a section.
+fn section_from_summary(summary: &str) -> Option<String> {
+    summary.split_whitespace().skip(1).find_map(|word| {
+        let word = word
+            .trim_matches(|c: char| !c.is_alphanumeric())
+            .to_lowercase();
+        SECTIONS
+            .iter()
+            .find(|(_, names)| names.contains(&word.as_str()))
+            .map(|(section, _)| section.to_string())
+    })
+}
+
+/// Pulls the instructor from `ORGANIZER;CN=...` or an `Instructor:` line in the description.
+fn instructor(event: &Event) -> Option<String> {
+    if let Some(name) = event.get("ORGANIZER").and_then(|p| p.params.get("CN")) {
+        return Some(name.clone());
+    }
+
+    let description = unescape_text(&event.get("DESCRIPTION")?.value);
+    description.lines().find_map(|line| {
+        let (key, value) = line.split_once(':')?;
+        if key.trim().eq_ignore_ascii_case("instructor") {
+            Some(value.trim().to_string())
+        } else {
+            None
+        }
+    })
+}
+
+/// Converts recurring weekly VEVENTs into a class schedule. A meeting type in the summary (e.g.
+/// "Lab" in "CS201 Lab") becomes the meeting's section.
+fn schedule_from_events(events: &[Event], class_name: &str) -> Result<ClassSchedule> {
+    // Meetings with the date they start on, so alternating weeks can be lined up with the term
+    let mut meetings: Vec<(ClassDay, NaiveDate)> = Vec::new();
+    let mut start_date: Option<NaiveDate> = None;
+    let mut end_date: Option<NaiveDate> = None;
+
+    for event in events {
+        let summary = event
+            .get("SUMMARY")
+            .map(|p| unescape_text(&p.value))
+            .unwrap_or_default();
+        let start = match event.get("DTSTART") {
+            Some(p) if p.value.len() > 8 => parse_datetime(&p.value)?,
+            _ => {
+                println!("Skipping all-day or undated event: {}", summary);
+                continue;
+            }
+        };
+        let end = match event.get("DTEND") {
+            Some(p) => parse_datetime(&p.value)?,
+            None => start,
+        };
+
+        // Split RRULE parts like FREQ=WEEKLY;BYDAY=MO,WE;UNTIL=20240515T235959Z
+        let rule: HashMap<String, String> = event
+            .get("RRULE")
+            .map(|p| {
+                p.value
+                    .split(';')
+                    .filter_map(|part| part.split_once('='))
+                    .map(|(k, v)| (k.to_uppercase(), v.to_string()))
+                    .collect()
+            })
+            .unwrap_or_default();
+        if rule.get("FREQ").is_some_and(|f| f != "WEEKLY") {
+            println!("Skipping non-weekly event: {}", summary);
+            continue;
+        }
+
+        let weekdays: Vec<Weekday> = match rule.get("BYDAY") {
+            Some(days) => days.split(',').filter_map(weekday_from_ics).collect(),
+            None => vec![Weekday::from(start.weekday())],
+        };
+
+        let week_interval: u32 = match rule.get("INTERVAL") {
+            Some(interval) => interval
+                .parse()
+                .with_context(|| format!("Invalid INTERVAL in RRULE: {}", interval))?,
+            None => 1,
+        };
+
+        let last_date = if let Some(until) = rule.get("UNTIL") {
+            parse_datetime(until)?.date()
+        } else if let Some(count) = rule.get("COUNT") {
+            let count: usize = count
+                .parse()
+                .with_context(|| format!("Invalid COUNT in RRULE: {}", count))?;
+            // Occurrences only fall in every INTERVAL-th week counted from the first one
+            let interval = week_interval.max(1) as i64;
+            start
+                .date()
+                .iter_days()
+                .filter(|d| weekdays.contains(&Weekday::from(d.weekday())))
+                .filter(|d| week_number(start.date(), *d) % interval == 0)
+                .take(count)
+                .last()
+                .unwrap_or(start.date())
+        } else if rule.is_empty() {
+            start.date()
+        } else {
+            anyhow::bail!("Recurring event without UNTIL or COUNT: {}", summary);
+        };
+
+        start_date = Some(start_date.map_or(start.date(), |d| d.min(start.date())));
+        end_date = Some(end_date.map_or(last_date, |d| d.max(last_date)));
+
+        // EXDATEs cancel this event only, so they stay with its meetings rather than the class
+        let mut exdates = Vec::new();
+        for property in event.get_all("EXDATE") {
+            for value in property.value.split(',') {
+                exdates.push(parse_datetime(value)?.date());
+            }
+        }
+        exdates.sort();
+        exdates.dedup();
+
+        let section = section_from_summary(&summary);
+        let location = event.get("LOCATION").map(|p| unescape_text(&p.value));
+        let instructor = instructor(event);
+        for weekday in weekdays {
+            let exclusions = exdates
+                .iter()
+                .filter(|d| Weekday::from(d.weekday()) == weekday)
+                .map(|d| Exclusion::Date(d.format("%Y-%m-%d").to_string()))
+                .collect();
+            meetings.push((
+                ClassDay {
+                    weekday,
+                    start_time: start.format("%H:%M").to_string(),
+                    end_time: end.format("%H:%M").to_string(),
+                    location: location.clone(),
+                    instructor: instructor.clone(),
+                    section: section.clone(),
+                    week_interval,
+                    week_offset: 0,
+                    exclusions,
+                },
+                start.date(),
+            ));
+        }
+    }
+
+    let (start_date, end_date) = match (start_date, end_date) {
+        (Some(start), Some(end)) => (start, end),
+        _ => anyhow::bail!("No recurring class events found in calendar"),
+    };
+
+    let schedule = meetings
+        .into_iter()
+        .map(|(mut day, first_date)| {
+            let interval = day.week_interval.max(1) as i64;
+            day.week_offset = week_number(start_date, first_date).rem_euclid(interval) as u32;
+            day
+        })
+        .collect();
+
+    Ok(ClassSchedule {
+        class_name: class_name.to_string(),
+        term: None,
+        start_date: start_date.format("%Y-%m-%d").to_string(),
+        end_date: end_date.format("%Y-%m-%d").to_string(),
+        schedule,
+        exclusions: Vec::new(),
+        extra_days: Vec::new(),
+        overrides: Vec::new(),
+    })
+}
+
+pub fn import_schedule(ics_path: &str, class: Option<&str>, force: bool) -> Result<()> {
+    let contents = fs::read_to_string(ics_path)
+        .with_context(|| format!("Failed to read calendar file: {}", ics_path))?;
+    let events = parse_events(&contents);
+
+    // Default the class name to the first word of the first event's summary (e.g. "CS201 Lecture")
+    let class_name = match class {
+        Some(class) => class.to_string(),
+        None => events
+            .iter()
+            .find_map(|e| e.get("SUMMARY"))
+            .and_then(|p| {
+                unescape_text(&p.value)
+                    .split_whitespace()
+                    .next()
+                    .map(String::from)
+            })
+            .ok_or_else(|| anyhow::anyhow!("No class name found; pass --class"))?,
+    };
+
+    let schedule = schedule_from_events(&events, &class_name)?;
+
+    let schedule_path = get_schedule_path(&class_name)?;
+    if Path::new(&schedule_path).exists() && !force {
+        anyhow::bail!(
+            "Schedule already exists: {} (use --force to overwrite)",
+            schedule_path
+        );
+    }
+    fs::write(
+        &schedule_path,
+        serde_json::to_string_pretty(&schedule)? + "\n",
+    )
+    .with_context(|| format!("Failed to write schedule file: {}", schedule_path))?;
+
+    println!(
+        "Imported {} ({} meetings per week, {} to {}) into {}",
+        schedule.class_name,
+        schedule.schedule.len(),
+        schedule.start_date,
+        schedule.end_date,
+        schedule_path
+    );
+    Ok(())
+}
+
+fn weekday_to_ics(weekday: &Weekday) -> &'static str {
+    match weekday {
+        Weekday::Monday => "MO",
+        Weekday::Tuesday => "TU",
+        Weekday::Wednesday => "WE",
+        Weekday::Thursday => "TH",
+        Weekday::Friday => "FR",
+        Weekday::Saturday => "SA",
+        Weekday::Sunday => "SU",
+    }
+}
+
+fn escape_text(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace('\n', "\\n")
+}
+
+/// Folds a content line so no physical line is longer than 75 octets.
+fn fold_line(line: &str) -> String {
+    let mut folded = String::new();
+    let mut length = 0;
+    for c in line.chars() {
+        if length + c.len_utf8() > 75 {
+            folded.push_str("\r\n ");
+            length = 1;
+        }
+        folded.push(c);
+        length += c.len_utf8();
+    }
+    folded.push_str("\r\n");
+    folded
+}
+
+fn calendar(events: &[Vec<String>]) -> String {
+    let mut output = String::new();
+    for line in [
+        "BEGIN:VCALENDAR",
+        "VERSION:2.0",
+        "PRODID:-//journal-automation//ja//EN",
+    ] {
+        output.push_str(&fold_line(line));
+    }
+    for event in events {
+        output.push_str(&fold_line("BEGIN:VEVENT"));
+        for line in event {
+            output.push_str(&fold_line(line));
+        }
+        output.push_str(&fold_line("END:VEVENT"));
+    }
+    output.push_str(&fold_line("END:VCALENDAR"));
+    output
+}
+
+/// Builds one weekly VEVENT per distinct meeting (section, time, room, instructor and week
+/// pattern), listing every weekday it happens on in BYDAY.
+fn schedule_to_events(schedule: &ClassSchedule) -> Result<Vec<Vec<String>>> {
+    let start_date = NaiveDate::parse_from_str(&schedule.start_date, "%Y-%m-%d")
+        .with_context(|| format!("Invalid start date format: {}", schedule.start_date))?;
+    let end_date = NaiveDate::parse_from_str(&schedule.end_date, "%Y-%m-%d")
+        .with_context(|| format!("Invalid end date format: {}", schedule.end_date))?;
+    let stamp = Utc::now().format("%Y%m%dT%H%M%SZ").to_string();
+
+    // Each meeting's own exclusions are kept with it, limited to the weekday they belong to
+    let mut meetings: Vec<(&ClassDay, Vec<Weekday>, Vec<NaiveDate>)> = Vec::new();
+    for day in &schedule.schedule {
+        let mut cancelled = Vec::new();
+        for exclusion in &day.exclusions {
+            cancelled.extend(
+                exclusion
+                    .dates()?
+                    .into_iter()
+                    .filter(|d| Weekday::from(d.weekday()) == day.weekday),
+            );
+        }
+        match meetings.iter_mut().find(|(m, _, _)| {
+            m.start_time == day.start_time
+                && m.end_time == day.end_time
+                && m.location == day.location
+                && m.instructor == day.instructor
+                && m.section == day.section
+                && m.week_interval == day.week_interval
+                && m.week_offset == day.week_offset
+        }) {
+            Some((_, weekdays, dates)) => {
+                weekdays.push(day.weekday.clone());
+                dates.extend(cancelled);
+            }
+            None => meetings.push((day, vec![day.weekday.clone()], cancelled)),
+        }
+    }
+
+    let excluded = schedule.excluded_dates()?;
+    let mut events = Vec::new();
+    for (meeting, weekdays, cancelled) in meetings {
+        let start_time = NaiveTime::parse_from_str(&meeting.start_time, "%H:%M")
+            .with_context(|| format!("Invalid start time format: {}", meeting.start_time))?;
+        let end_time = NaiveTime::parse_from_str(&meeting.end_time, "%H:%M")
+            .with_context(|| format!("Invalid end time format: {}", meeting.end_time))?;
+
+        // The first occurrence has to fall on one of the BYDAY weekdays in a week the meeting
+        // happens; INTERVAL then counts weeks from there
+        let meets = |d: &NaiveDate| {
+            weekdays.contains(&Weekday::from(d.weekday()))
+                && meeting.meets_in_week(week_number(start_date, *d))
+        };
+        let first_date = match start_date
+            .iter_days()
+            .take_while(|d| *d <= end_date)
+            .find(|d| meets(d))
+        {
+            Some(date) => date,
+            None => continue,
+        };
+
+        let byday: Vec<&str> = weekdays.iter().map(weekday_to_ics).collect();
+        let summary = summary(&schedule.class_name, meeting.section.as_deref());
+        let interval = if meeting.week_interval > 1 {
+            format!(";INTERVAL={}", meeting.week_interval)
+        } else {
+            String::new()
+        };
+        // Alternating meetings at the same time differ only in the weeks they start on
+        let mut uid = format!(
+            "{}-{}-{}",
+            summary.replace(' ', "_"),
+            byday.join(""),
+            start_time.format("%H%M")
+        );
+        if meeting.week_interval > 1 {
+            uid.push_str(&format!("-w{}", meeting.week_offset));
+        }
+        let mut lines = vec![
+            format!("UID:{}@journal-automation", uid),
+            format!("DTSTAMP:{}", stamp),
+            format!("SUMMARY:{}", escape_text(&summary)),
+            format!(
+                "DTSTART:{}",
+                first_date.and_time(start_time).format("%Y%m%dT%H%M%S")
+            ),
+            format!(
+                "DTEND:{}",
+                first_date.and_time(end_time).format("%Y%m%dT%H%M%S")
+            ),
+            format!(
+                "RRULE:FREQ=WEEKLY{};BYDAY={};UNTIL={}T235959",
+                interval,
+                byday.join(","),
+                end_date.format("%Y%m%d")
+            ),
+        ];
+
+        // Excluded, overridden and makeup dates are left out of the recurrence; overridden and
+        // makeup dates are exported as their own events below, with every session on that day
+        let mut skipped: Vec<NaiveDate> = excluded.iter().copied().collect();
+        skipped.extend(cancelled);
+        for date_override in &schedule.overrides {
+            skipped.push(parse_schedule_date(&date_override.date)?);
+        }
+        for extra in &schedule.extra_days {
+            skipped.push(parse_schedule_date(&extra.date)?);
+        }
+        skipped.sort();
+        skipped.dedup();
+        let exdates: Vec<String> = skipped
+            .into_iter()
+            .filter(|d| *d >= start_date && *d <= end_date)
+            .filter(|d| meets(d))
+            .map(|d| d.and_time(start_time).format("%Y%m%dT%H%M%S").to_string())
+            .collect();
+        if !exdates.is_empty() {
+            lines.push(format!("EXDATE:{}", exdates.join(",")));
+        }
+        if let Some(location) = &meeting.location {
+            lines.push(format!("LOCATION:{}", escape_text(location)));
+        }
+        if let Some(instructor) = &meeting.instructor {
+            lines.push(format!(
+                "DESCRIPTION:{}",
+                escape_text(&format!("Instructor: {}", instructor))
+            ));
+        }
+
+        events.push(lines);
+    }
+
+    // One-off events for overridden dates and makeup sessions
+    let mut single_dates: Vec<NaiveDate> = Vec::new();
+    for date_override in &schedule.overrides {
+        single_dates.push(parse_schedule_date(&date_override.date)?);
+    }
+    for extra in &schedule.extra_days {
+        single_dates.push(parse_schedule_date(&extra.date)?);
+    }
+    single_dates.sort();
+    single_dates.dedup();
+    let resolved = schedule.resolve()?;
+    for date in single_dates {
+        for session in resolved.sessions_on(date) {
+            events.push(session_event(&schedule.class_name, &session, &stamp)?);
+        }
+    }
+
+    Ok(events)
+}
+
+/// Event title such as "CS201" or "CS201 Lab".
+fn summary(class_name: &str, section: Option<&str>) -> String {
+    match section {
+        Some(section) => format!("{} {}", class_name, section),
+        None => class_name.to_string(),
+    }
+}
+
+fn parse_schedule_date(value: &str) -> Result<NaiveDate> {
+    NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .with_context(|| format!("Invalid date format: {}", value))
+}
+
+fn session_event(class_name: &str, session: &Session, stamp: &str) -> Result<Vec<String>> {
+    let start_time = NaiveTime::parse_from_str(&session.start_time, "%H:%M")
+        .with_context(|| format!("Invalid start time format: {}", session.start_time))?;
+    let end_time = NaiveTime::parse_from_str(&session.end_time, "%H:%M")
+        .with_context(|| format!("Invalid end time format: {}", session.end_time))?;
+    let start = session.date.and_time(start_time);
+
+    let summary = summary(class_name, session.section.as_deref());
+    let mut lines = vec![
+        format!(
+            "UID:{}-{}@journal-automation",
+            class_name,
+            start.format("%Y%m%dT%H%M")
+        ),
+        format!("DTSTAMP:{}", stamp),
+        format!("SUMMARY:{}", escape_text(&summary)),
+        format!("DTSTART:{}", start.format("%Y%m%dT%H%M%S")),
+        format!(
+            "DTEND:{}",
+            session.date.and_time(end_time).format("%Y%m%dT%H%M%S")
+        ),
+    ];
+    if let Some(location) = &session.location {
+        lines.push(format!("LOCATION:{}", escape_text(location)));
+    }
+    if let Some(instructor) = &session.instructor {
+        lines.push(format!(
+            "DESCRIPTION:{}",
+            escape_text(&format!("Instructor: {}", instructor))
+        ));
+    }
+    Ok(lines)
+}
+
+pub fn export_schedule(class: &str) -> Result<()> {
+    let schedule = ClassSchedule::from_file(&get_schedule_path(class)?)?;
+
+    print!("{}", calendar(&schedule_to_events(&schedule)?));
+    Ok(())
+}
+
+/// Exports an all-day event for every entry with content in the given notebooks, linking back to
+/// the file.
+pub fn export_journal_days(year_filter: Option<u32>, notebooks: &[String]) -> Result<()> {
+    let stamp = Utc::now().format("%Y%m%dT%H%M%SZ").to_string();
+    let mut files = Vec::new();
+    for notebook in notebooks {
+        files.extend(process_journal_files(notebook)?);
+    }
+    files.sort_by_key(|f| (f.year, f.month, f.day, f.notebook != "journal"));
+
+    let mut events = Vec::new();
+    for file in files {
+        if year_filter.is_some_and(|year| file.year != year as i32) {
+            continue;
+        }
+        let date = match file.date() {
+            Some(date) => date,
+            None => continue,
+        };
+        let contents = fs::read_to_string(&file.path).unwrap_or_default();
+        if contents.trim().is_empty() {
+            continue;
+        }
+
+        let words = contents.split_whitespace().count();
+        let path = file.path.to_string_lossy();
+        let title = if file.notebook == "journal" {
+            "Journal".to_string()
+        } else {
+            format!("{} notes", file.notebook)
+        };
+        events.push(vec![
+            format!(
+                "UID:{}-{}@journal-automation",
+                file.notebook.replace(' ', "_"),
+                date.format("%Y%m%d")
+            ),
+            format!("DTSTAMP:{}", stamp),
+            format!("DTSTART;VALUE=DATE:{}", date.format("%Y%m%d")),
+            format!(
+                "DTEND;VALUE=DATE:{}",
+                (date + Duration::days(1)).format("%Y%m%d")
+            ),
+            format!(
+                "SUMMARY:{}",
+                escape_text(&format!("📓 {} ({} words)", title, words))
+            ),
+            format!("URL:file://{}", path.replace(' ', "%20")),
+            format!("DESCRIPTION:{}", escape_text(&path)),
+            "TRANSP:TRANSPARENT".to_string(),
+        ]);
+    }
+
+    print!("{}", calendar(&events));
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_schedule_from_events() {
+        let ics = "BEGIN:VCALENDAR\r\n\
+VERSION:2.0\r\n\
+BEGIN:VEVENT\r\n\
+SUMMARY:CS201 Lecture\r\n\
+DTSTART;TZID=America/Chicago:20240115T100000\r\n\
+DTEND;TZID=America/Chicago:20240115T113000\r\n\
+RRULE:FREQ=WEEKLY;BYDAY=MO,WE;UNTIL=20240515T235959\r\n\
+EXDATE;TZID=America/Chicago:20240311T100000,20240313T100000\r\n\
+LOCATION:Siebel Center\\, Room 1404\r\n\
+DESCRIPTION:Data structures\\nInstructor: Dr. Smith\r\n\
+END:VEVENT\r\n\
+END:VCALENDAR\r\n";
+
+        let events = parse_events(ics);
+        let schedule = schedule_from_events(&events, "CS201").unwrap();
+
+        assert_eq!(schedule.start_date, "2024-01-15");
+        assert_eq!(schedule.end_date, "2024-05-15");
+        assert!(schedule.exclusions.is_empty());
+        assert_eq!(schedule.schedule.len(), 2);
+        assert_eq!(
+            schedule.schedule[0].exclusions,
+            vec![Exclusion::Date("2024-03-11".to_string())]
+        );
+        assert_eq!(
+            schedule.schedule[1].exclusions,
+            vec![Exclusion::Date("2024-03-13".to_string())]
+        );
+        assert_eq!(schedule.schedule[1].weekday, Weekday::Wednesday);
+        assert_eq!(schedule.schedule[0].start_time, "10:00");
+        assert_eq!(schedule.schedule[0].end_time, "11:30");
+        assert_eq!(
+            schedule.schedule[0].location.as_deref(),
+            Some("Siebel Center, Room 1404")
+        );
+        assert_eq!(
+            schedule.schedule[0].instructor.as_deref(),
+            Some("Dr. Smith")
+        );
+
+        let dates = schedule.get_class_dates().unwrap();
+        assert!(!dates.contains(&NaiveDate::from_ymd_opt(2024, 3, 11).unwrap()));
+
+        // Exporting and importing again gives the same schedule
+        let exported = calendar(&schedule_to_events(&schedule).unwrap());
+        assert!(exported.contains("RRULE:FREQ=WEEKLY;BYDAY=MO,WE;UNTIL=20240515T235959\r\n"));
+        let reimported = schedule_from_events(&parse_events(&exported), "CS201").unwrap();
+        assert_eq!(reimported.get_class_dates().unwrap(), dates);
+        assert_eq!(
+            reimported.schedule[0].location,
+            schedule.schedule[0].location
+        );
+        assert_eq!(
+            reimported.schedule[0].instructor,
+            schedule.schedule[0].instructor
+        );
+    }
+
+    #[test]
+    fn test_alternating_week_round_trip() {
+        let json = r#"{
+            "class_name": "CHEM101",
+            "start_date": "2024-01-17",
+            "end_date": "2024-03-01",
+            "schedule": [
+                { "weekday": "friday", "start_time": "09:00", "end_time": "10:00", "section": "Lecture" },
+                {
+                    "weekday": "friday",
+                    "start_time": "13:00",
+                    "end_time": "16:00",
+                    "section": "Lab",
+                    "week_interval": 2,
+                    "week_offset": 1
+                }
+            ]
+        }"#;
+        let schedule: ClassSchedule = serde_json::from_str(json).unwrap();
+
+        let exported = calendar(&schedule_to_events(&schedule).unwrap());
+        assert!(exported.contains("SUMMARY:CHEM101 Lab\r\n"));
+        assert!(exported.contains("DTSTART:20240126T130000\r\n"));
+        assert!(
+            exported.contains("RRULE:FREQ=WEEKLY;INTERVAL=2;BYDAY=FR;UNTIL=20240301T235959\r\n")
+        );
+
+        let reimported = schedule_from_events(&parse_events(&exported), "CHEM101").unwrap();
+        let lab = &reimported.schedule[1];
+        assert_eq!(lab.section.as_deref(), Some("Lab"));
+        assert_eq!((lab.week_interval, lab.week_offset), (2, 1));
+        for date in schedule.get_class_dates().unwrap() {
+            assert_eq!(
+                reimported.sessions_on(date).unwrap(),
+                schedule.sessions_on(date).unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn test_exdate_cancels_only_its_meeting() {
+        let ics = "BEGIN:VEVENT\r\n\
+SUMMARY:CS201 Lecture\r\n\
+DTSTART:20240115T100000\r\n\
+DTEND:20240115T113000\r\n\
+RRULE:FREQ=WEEKLY;BYDAY=MO;UNTIL=20240205T235959\r\n\
+END:VEVENT\r\n\
+BEGIN:VEVENT\r\n\
+SUMMARY:CS201 Lab\r\n\
+DTSTART:20240115T140000\r\n\
+DTEND:20240115T160000\r\n\
+RRULE:FREQ=WEEKLY;BYDAY=MO;UNTIL=20240205T235959\r\n\
+EXDATE:20240122T140000\r\n\
+END:VEVENT\r\n";
+
+        let schedule = schedule_from_events(&parse_events(ics), "CS201").unwrap();
+        let cancelled = NaiveDate::from_ymd_opt(2024, 1, 22).unwrap();
+        let sessions = schedule.sessions_on(cancelled).unwrap();
+        assert_eq!(sessions.len(), 1);
+        assert_eq!(sessions[0].section.as_deref(), Some("Lecture"));
+        assert!(schedule.get_class_dates().unwrap().contains(&cancelled));
+
+        let exported = calendar(&schedule_to_events(&schedule).unwrap());
+        assert_eq!(exported.matches("EXDATE:20240122T140000\r\n").count(), 1);
+        let reimported = schedule_from_events(&parse_events(&exported), "CS201").unwrap();
+        assert_eq!(reimported.sessions_on(cancelled).unwrap(), sessions);
+    }
+
+    #[test]
+    fn test_extra_day_on_a_meeting_day() {
+        let json = r#"{
+            "class_name": "CS201",
+            "start_date": "2024-03-04",
+            "end_date": "2024-03-18",
+            "schedule": [{ "weekday": "monday", "start_time": "10:00", "end_time": "11:00" }],
+            "extra_days": [{ "date": "2024-03-11", "start_time": "15:00", "end_time": "16:00" }]
+        }"#;
+        let schedule: ClassSchedule = serde_json::from_str(json).unwrap();
+
+        // The regular session is exported once, as a one-off next to the makeup session
+        let exported = calendar(&schedule_to_events(&schedule).unwrap());
+        assert_eq!(exported.matches("DTSTART:20240311T100000\r\n").count(), 1);
+        assert_eq!(exported.matches("DTSTART:20240311T150000\r\n").count(), 1);
+        assert!(exported.contains("EXDATE:20240311T100000\r\n"));
+
+        let reimported = schedule_from_events(&parse_events(&exported), "CS201").unwrap();
+        let date = NaiveDate::from_ymd_opt(2024, 3, 11).unwrap();
+        assert_eq!(reimported.sessions_on(date).unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_count_with_interval() {
+        let ics = "BEGIN:VEVENT\r\n\
+SUMMARY:CHEM101 Lab\r\n\
+DTSTART:20240105T130000\r\n\
+DTEND:20240105T160000\r\n\
+RRULE:FREQ=WEEKLY;INTERVAL=2;COUNT=4;BYDAY=FR\r\n\
+END:VEVENT\r\n";
+
+        let schedule = schedule_from_events(&parse_events(ics), "CHEM101").unwrap();
+        assert_eq!(schedule.end_date, "2024-02-16");
+        let date = |m, d| NaiveDate::from_ymd_opt(2024, m, d).unwrap();
+        assert_eq!(
+            schedule.get_class_dates().unwrap(),
+            vec![date(1, 5), date(1, 19), date(2, 2), date(2, 16)]
+        );
+    }
+
+    #[test]
+    fn test_section_from_summary() {
+        assert_eq!(section_from_summary("CS201 Lab").as_deref(), Some("Lab"));
+        assert_eq!(
+            section_from_summary("CS 201 LEC A").as_deref(),
+            Some("Lecture")
+        );
+        assert_eq!(
+            section_from_summary("CHEM101 (Discussion)").as_deref(),
+            Some("Discussion")
+        );
+        assert_eq!(section_from_summary("Intro to CS"), None);
+        assert_eq!(section_from_summary("CS201"), None);
+    }
+}
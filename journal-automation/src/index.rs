@@ -0,0 +1,375 @@
+use crate::header::table_fields;
+use crate::notebook::{walk_notebook, JournalFile};
+use crate::utils::get_git_root;
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+    time::UNIX_EPOCH,
+};
+
+// Bump when the stored fields change so old indexes are rebuilt instead of misread
+const INDEX_VERSION: u32 = 4;
+
+/// What the analysis commands need from an entry, cached in `.ja/index` and only recomputed when
+/// the file's size or modification time changes.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EntryInfo {
+    pub size: u64,
+    pub mtime_ns: u64,
+    pub hash: String, // FNV-1a of the contents
+    pub words: u64,
+    pub lines: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub header: Option<String>, // first "# " line without the marker
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>, // Obsidian #tags, lowercased, in order of appearance
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub fields: BTreeMap<String, String>, // table columns such as weather, mood and sleep
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub links: Vec<String>, // targets of markdown and [[wiki]] links, in order of appearance
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct Index {
+    version: u32,
+    // notebook -> path relative to the notebook -> info
+    notebooks: BTreeMap<String, BTreeMap<String, EntryInfo>>,
+}
+
+fn index_path() -> Result<PathBuf> {
+    Ok(Path::new(&get_git_root()?).join(".ja/index"))
+}
+
+fn load_index(path: &Path) -> Index {
+    // A missing, unreadable or outdated index is simply rebuilt
+    fs::read_to_string(path)
+        .ok()
+        .and_then(|contents| serde_json::from_str::<Index>(&contents).ok())
+        .filter(|index| index.version == INDEX_VERSION)
+        .unwrap_or_default()
+}
+
+fn save_index(path: &Path, index: &Index) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    // Write next to the index and rename so an interrupted run can't leave half a file
+    let tmp = path.with_extension("tmp");
+    fs::write(&tmp, serde_json::to_string(index)?)
+        .with_context(|| format!("Failed to write index: {}", tmp.display()))?;
+    fs::rename(&tmp, path).with_context(|| format!("Failed to write index: {}", path.display()))?;
+    Ok(())
+}
+
+fn fnv1a(bytes: &[u8]) -> String {
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for byte in bytes {
+        hash ^= *byte as u64;
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+    format!("{:016x}", hash)
+}
+
+fn read_info(path: &Path, size: u64, mtime_ns: u64) -> Result<EntryInfo> {
+    let bytes = fs::read(path).with_context(|| format!("Failed to read {}", path.display()))?;
+    Ok(info_from_bytes(&bytes, size, mtime_ns))
+}
+
+fn info_from_bytes(bytes: &[u8], size: u64, mtime_ns: u64) -> EntryInfo {
+    let contents = String::from_utf8_lossy(bytes);
+    EntryInfo {
+        size,
+        mtime_ns,
+        hash: fnv1a(bytes),
+        words: contents.split_whitespace().count() as u64,
+        lines: contents.lines().count() as u64,
+        header: contents
+            .lines()
+            .find(|line| line.starts_with("# "))
+            .map(|h| h.trim_start_matches("# ").trim().to_string()),
+        tags: find_tags(&contents),
+        fields: table_fields(&contents),
+        links: find_links(&contents),
+    }
+}
+
+/// Targets of `[text](target)` and `[[target|alias]]` links, e.g. the previous and next day.
+pub fn find_links(contents: &str) -> Vec<String> {
+    let mut links = Vec::new();
+    let mut rest = contents;
+    while let Some(start) = rest.find('[') {
+        rest = &rest[start..];
+        let target = if let Some(wiki) = rest.strip_prefix("[[") {
+            wiki.find("]]").map(|end| {
+                let inner = &wiki[..end];
+                (inner.split('|').next().unwrap_or(inner), end + 4)
+            })
+        } else {
+            rest.find("](").and_then(|close| {
+                let after = &rest[close + 2..];
+                after
+                    .find(')')
+                    .filter(|_| !rest[1..close].contains('\n'))
+                    .map(|end| (&after[..end], close + 2 + end + 1))
+            })
+        };
+        match target {
+            Some((target, consumed)) => {
+                if !target.trim().is_empty() {
+                    links.push(target.trim().to_string());
+                }
+                rest = &rest[consumed..];
+            }
+            None => rest = &rest[1..],
+        }
+    }
+    links
+}
+
+/// Words such as `#travel` or `#people/anna`. Headings and numbers like `#1` are not tags.
+pub fn find_tags(contents: &str) -> Vec<String> {
+    let mut tags = Vec::new();
+    for word in contents.split_whitespace() {
+        let Some(tag) = word.strip_prefix('#') else {
+            continue;
+        };
+        let tag: String = tag
+            .chars()
+            .take_while(|c| c.is_alphanumeric() || matches!(c, '_' | '-' | '/'))
+            .collect();
+        let tag = tag.trim_end_matches('/').to_lowercase();
+        if !tag.is_empty() && !tag.chars().all(|c| c.is_ascii_digit()) && !tags.contains(&tag) {
+            tags.push(tag);
+        }
+    }
+    tags
+}
+
+/// Reads the given files on all cores, keeping their order.
+fn read_all(jobs: &[(PathBuf, u64, u64)]) -> Vec<Result<EntryInfo>> {
+    let threads = std::thread::available_parallelism()
+        .map(|n| n.get())
+        .unwrap_or(1);
+    let chunk_size = jobs.len().div_ceil(threads).max(1);
+    std::thread::scope(|scope| {
+        let handles: Vec<_> = jobs
+            .chunks(chunk_size)
+            .map(|chunk| {
+                scope.spawn(move || {
+                    chunk
+                        .iter()
+                        .map(|(path, size, mtime_ns)| read_info(path, *size, *mtime_ns))
+                        .collect::<Vec<_>>()
+                })
+            })
+            .collect();
+        handles
+            .into_iter()
+            .flat_map(|handle| handle.join().expect("index reader thread panicked"))
+            .collect()
+    })
+}
+
+/// A file on disk with the size and modification time that decide whether its cached info is
+/// still good.
+struct FoundFile {
+    key: String,
+    path: PathBuf,
+    size: u64,
+    mtime_ns: u64,
+}
+
+/// Reuses cached info for files whose size and modification time haven't changed and reads the
+/// rest. Files that can't be read are reported and come back as `None`.
+fn update_infos(
+    cached: &BTreeMap<String, EntryInfo>,
+    found: &[FoundFile],
+) -> (Vec<Option<EntryInfo>>, usize) {
+    let mut infos: Vec<Option<EntryInfo>> = Vec::with_capacity(found.len());
+    let mut pending = Vec::new();
+    let mut jobs = Vec::new();
+    for (i, file) in found.iter().enumerate() {
+        match cached.get(&file.key) {
+            Some(info) if info.size == file.size && info.mtime_ns == file.mtime_ns => {
+                infos.push(Some(info.clone()));
+            }
+            _ => {
+                infos.push(None);
+                pending.push(i);
+                jobs.push((file.path.clone(), file.size, file.mtime_ns));
+            }
+        }
+    }
+
+    for (i, info) in pending.into_iter().zip(read_all(&jobs)) {
+        match info {
+            Ok(info) => infos[i] = Some(info),
+            Err(e) => eprintln!("Skipping {}: {:#}", found[i].path.display(), e),
+        }
+    }
+    (infos, jobs.len())
+}
+
+/// Returns every entry in a notebook with its cached info, reading only new or changed files.
+/// Entries that can't be read are reported and left out.
+pub fn indexed_entries(notebook: &str) -> Result<Vec<(JournalFile, EntryInfo)>> {
+    let path = index_path()?;
+    let mut index = load_index(&path);
+    let cached = index.notebooks.remove(notebook).unwrap_or_default();
+    let notebook_root = Path::new(&get_git_root()?).join(notebook);
+
+    let mut files = Vec::new();
+    let mut found = Vec::new();
+    for file in walk_notebook(notebook)? {
+        let metadata = match fs::metadata(&file.path) {
+            Ok(metadata) => metadata,
+            Err(e) => {
+                eprintln!("Skipping {}: {}", file.path.display(), e);
+                continue;
+            }
+        };
+        let mtime_ns = metadata
+            .modified()
+            .ok()
+            .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+            .map(|d| d.as_nanos() as u64)
+            .unwrap_or(0);
+        found.push(FoundFile {
+            key: relative_key(&notebook_root, &file.path),
+            path: file.path.clone(),
+            size: metadata.len(),
+            mtime_ns,
+        });
+        files.push(file);
+    }
+    let (infos, read) = update_infos(&cached, &found);
+
+    // Rebuilding the notebook's map also drops files that no longer exist
+    let entries: Vec<(JournalFile, EntryInfo)> = files
+        .into_iter()
+        .zip(infos)
+        .filter_map(|(file, info)| info.map(|info| (file, info)))
+        .collect();
+    let notebook_index = entries
+        .iter()
+        .map(|(file, info)| (relative_key(&notebook_root, &file.path), info.clone()))
+        .collect();
+    index.version = INDEX_VERSION;
+    index.notebooks.insert(notebook.to_string(), notebook_index);
+    if read > 0 || cached.len() != entries.len() {
+        save_index(&path, &index)?;
+    }
+
+    Ok(entries)
+}
+
+fn relative_key(notebook_root: &Path, path: &Path) -> String {
+    path.strip_prefix(notebook_root)
+        .unwrap_or(path)
+        .to_string_lossy()
+        .replace('\\', "/")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_info_from_bytes() {
+        let contents = b"Some text\n# Monday, January 15, 2024\n\nTwo words\n";
+        let info = info_from_bytes(contents, contents.len() as u64, 0);
+        assert_eq!(info.words, 9);
+        assert_eq!(info.lines, 4);
+        assert_eq!(info.header.as_deref(), Some("Monday, January 15, 2024"));
+
+        assert_eq!(fnv1a(b""), "cbf29ce484222325");
+        assert_eq!(fnv1a(b"a"), "af63dc4c8601ec8c");
+        assert_eq!(info_from_bytes(b"", 0, 0).header, None);
+    }
+
+    #[test]
+    fn test_find_tags() {
+        let contents = "# Monday
+## Notes
+Hiked with #Anna at #travel/alps, issue #42.
+#travel/alps again";
+        assert_eq!(find_tags(contents), vec!["anna", "travel/alps"]);
+    }
+
+    #[test]
+    fn test_find_links() {
+        let contents = "[Previous](14_Sunday.md) | [[16_Tuesday|Next]]
+See [the trip](../02-feb/10_Saturday.md), [not a link] and [[March 2024 Happenings]].
+[empty]() [unclosed](oops";
+        assert_eq!(
+            find_links(contents),
+            vec![
+                "14_Sunday.md",
+                "16_Tuesday",
+                "../02-feb/10_Saturday.md",
+                "March 2024 Happenings"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_update_infos_reuses_and_invalidates() {
+        let dir = std::env::temp_dir().join(format!("ja-index-test-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let found = |name: &str| {
+            let path = dir.join(name);
+            let metadata = fs::metadata(&path).unwrap();
+            FoundFile {
+                key: name.to_string(),
+                path,
+                size: metadata.len(),
+                mtime_ns: metadata
+                    .modified()
+                    .unwrap()
+                    .duration_since(UNIX_EPOCH)
+                    .unwrap()
+                    .as_nanos() as u64,
+            }
+        };
+        for name in ["same.md", "resized.md", "touched.md"] {
+            fs::write(dir.join(name), "one two").unwrap();
+        }
+        let files: Vec<FoundFile> = ["same.md", "resized.md", "touched.md"]
+            .into_iter()
+            .map(found)
+            .collect();
+        let (infos, read) = update_infos(&BTreeMap::new(), &files);
+        assert_eq!(read, 3);
+
+        // Mark the cached infos so reused ones can be told apart from fresh reads
+        let cached: BTreeMap<String, EntryInfo> = files
+            .iter()
+            .zip(infos)
+            .map(|(file, info)| {
+                let mut info = info.unwrap();
+                info.hash = "cached".to_string();
+                (file.key.clone(), info)
+            })
+            .collect();
+
+        fs::write(dir.join("resized.md"), "one two three").unwrap();
+        let mut touched = found("touched.md");
+        touched.mtime_ns += 1;
+        let mut missing = found("same.md");
+        missing.key = "missing.md".to_string();
+        missing.path = dir.join("missing.md");
+        let files = vec![found("same.md"), found("resized.md"), touched, missing];
+        let (infos, read) = update_infos(&cached, &files);
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(read, 3);
+        assert_eq!(infos[0].as_ref().unwrap().hash, "cached");
+        assert_eq!(infos[1].as_ref().unwrap().words, 3);
+        assert_ne!(infos[1].as_ref().unwrap().hash, "cached");
+        assert_ne!(infos[2].as_ref().unwrap().hash, "cached");
+        assert!(infos[3].is_none());
+    }
+}
@@ -1,37 +1,30 @@
-use crate::schedule::ClassSchedule;
-use crate::utils::{get_device_info, get_git_root, get_location, get_weather, open_in_editor};
+use crate::chart;
+use crate::header::MISSING;
+use crate::index::{indexed_entries, EntryInfo};
+use crate::notebook::{
+    entry_file_name, legacy_month_folder, month_folder, walk_notebook, JournalFile,
+};
+use crate::schedule::{get_schedule_path, list_schedule_files, ClassSchedule};
+use crate::shuffle::{pick_spaced, Candidate};
+use crate::stats::{median, percentile, trend_per_year};
+use crate::utils::{
+    get_device_info, get_git_root, get_location, get_weather, open_in_editor, prompt_scale,
+};
 use anyhow::{Context, Result};
 use chrono::{Datelike, Local, NaiveDate};
-use rand::seq::SliceRandom;
 use std::{
-    collections::{HashMap, HashSet},
+    collections::{BTreeMap, HashMap, HashSet},
     fs::{self, File, OpenOptions},
-    io::Write,
-    path::PathBuf,
+    io::{ErrorKind, Write},
 };
 
 pub fn get_todays_journal_path(class: &str) -> Result<String> {
-    let today = Local::now();
-    let year = today.format("%Y").to_string();
-    let month = today.format("%m").to_string();
-    let month_name = today.format("%B").to_string();
-    let day = today.format("%d").to_string();
-    let weekday = today.format("%A").to_string();
-
-    let git_root = get_git_root()?;
-    Ok(format!(
-        "{}/{}/{}/{}-{}/{}_{}.md",
-        git_root,
-        class,
-        year,
-        month,
-        month_name.to_lowercase(),
-        day,
-        weekday
-    ))
+    get_journal_path_for_date(Local::now().date_naive(), class)
 }
 
-pub fn create_journal_entry(journal_path: &str, class: &str) -> Result<()> {
+/// Writes the header of a new entry. Journal entries ask for mood and energy when `ask_ratings`
+/// is set; otherwise both are recorded as missing.
+pub fn create_journal_entry(journal_path: &str, class: &str, ask_ratings: bool) -> Result<()> {
     if !std::path::Path::new(journal_path).exists() {
         anyhow::bail!("Journal file not found: {}", journal_path);
     }
@@ -46,19 +39,36 @@ pub fn create_journal_entry(journal_path: &str, class: &str) -> Result<()> {
         .open(journal_path)
         .context("Failed to open journal file")?;
 
+    // Ask before writing anything so an interrupted prompt leaves the file untouched
+    let (mood, energy) = if class == "journal" && ask_ratings {
+        (prompt_scale("Mood"), prompt_scale("Energy"))
+    } else {
+        (MISSING.to_string(), MISSING.to_string())
+    };
+
     writeln!(file, "# {}", date_text)?;
     writeln!(file)?; // Add an extra newline
 
-    // Only add device/location/weather for journal entries
+    // Only add device/location/weather/mood for journal entries
     if class == "journal" {
-        writeln!(file, "| device  | location     | weather    |")?;
-        writeln!(file, "| ------- | ------------ | ---------- |")?;
+        writeln!(
+            file,
+            "| device  | location     | weather    | mood | energy |"
+        )?;
+        writeln!(
+            file,
+            "| ------- | ------------ | ---------- | ---- | ------ |"
+        )?;
 
         let device = get_device_info();
         let location = get_location()?;
         let weather = get_weather(&location)?;
 
-        writeln!(file, "| {} | {} | {} |", device, location, weather)?;
+        writeln!(
+            file,
+            "| {} | {} | {} | {} | {} |",
+            device, location, weather, mood, energy
+        )?;
     } else {
         // For class notes, add class-specific header
         writeln!(file, "## Class Information")?;
@@ -66,22 +76,40 @@ pub fn create_journal_entry(journal_path: &str, class: &str) -> Result<()> {
         writeln!(file, "- Course: {}", class)?;
 
         // Try to load schedule for additional info
-        if let Ok(schedule) = ClassSchedule::from_file(&format!(
-            "{}/journal-automation/schedules/{}.json",
-            get_git_root()?,
-            class
-        )) {
-            if let Some(day) = schedule.schedule.iter().find(|d| {
-                d.weekday.to_string().to_lowercase()
-                    == today.format("%A").to_string().to_lowercase()
-            }) {
-                if let Some(location) = &day.location {
+        if let Ok(schedule) = ClassSchedule::from_file(&get_schedule_path(class)?) {
+            let sessions = schedule.sessions_on(today.date_naive())?;
+            if let [session] = sessions.as_slice() {
+                if let Some(section) = &session.section {
+                    writeln!(file, "- Section: {}", section)?;
+                }
+                if let Some(location) = &session.location {
                     writeln!(file, "- Location: {}", location)?;
                 }
-                if let Some(instructor) = &day.instructor {
+                if let Some(instructor) = &session.instructor {
                     writeln!(file, "- Instructor: {}", instructor)?;
                 }
-                writeln!(file, "- Time: {} - {}", day.start_time, day.end_time)?;
+                writeln!(
+                    file,
+                    "- Time: {} - {}",
+                    session.start_time, session.end_time
+                )?;
+            } else {
+                // Several meetings today (e.g. lecture and lab): one line each
+                for session in &sessions {
+                    let mut line = format!(
+                        "- {}: {} - {}",
+                        session.section.as_deref().unwrap_or("Session"),
+                        session.start_time,
+                        session.end_time
+                    );
+                    if let Some(location) = &session.location {
+                        line.push_str(&format!(", {}", location));
+                    }
+                    if let Some(instructor) = &session.instructor {
+                        line.push_str(&format!(" ({})", instructor));
+                    }
+                    writeln!(file, "{}", line)?;
+                }
             }
         }
         writeln!(file)?;
@@ -106,17 +134,7 @@ pub fn open_journal_entry_by_date(date_str: &str, class: &str) -> Result<()> {
     let date = NaiveDate::parse_from_str(date_str, "%Y-%m-%d")
         .with_context(|| format!("Invalid date format: {}. Expected YYYY-MM-DD", date_str))?;
 
-    let year = date.format("%Y").to_string();
-    let month = date.format("%m").to_string();
-    let month_name = date.format("%b").to_string().to_lowercase();
-    let day = date.format("%d").to_string();
-    let weekday = date.format("%A").to_string();
-
-    let git_root = get_git_root()?;
-    let journal_path = format!(
-        "{}/{}/{}/{}-{}/{}_{}.md",
-        git_root, class, year, month, month_name, day, weekday
-    );
+    let journal_path = get_journal_path_for_date(date, class)?;
 
     if !std::path::Path::new(&journal_path).exists() {
         anyhow::bail!("Journal file not found: {}", journal_path);
@@ -125,19 +143,50 @@ pub fn open_journal_entry_by_date(date_str: &str, class: &str) -> Result<()> {
     open_in_editor(&journal_path)
 }
 
-pub fn create_year(year: u32, class: &str) -> Result<()> {
+/// Builds the `{class}/YYYY/MM-mon` folder for a given date, relative to the git root. A month that
+/// only exists as an older `MM-month` folder keeps using that folder.
+pub fn get_month_folder(date: NaiveDate, class: &str) -> Result<String> {
     let git_root = get_git_root()?;
+    let folder = format!("{}/{}", class, month_folder(date));
+    let legacy = format!("{}/{}", class, legacy_month_folder(date));
+    let exists = |folder: &str| std::path::Path::new(&git_root).join(folder).exists();
+    if !exists(&folder) && exists(&legacy) {
+        return Ok(legacy);
+    }
+    Ok(folder)
+}
+
+/// Builds the `{class}/YYYY/MM-mon/DD_Weekday.md` path for a given date.
+pub fn get_journal_path_for_date(date: NaiveDate, class: &str) -> Result<String> {
+    let git_root = get_git_root()?;
+    Ok(format!(
+        "{}/{}/{}",
+        git_root,
+        get_month_folder(date, class)?,
+        entry_file_name(date)
+    ))
+}
+
+pub fn create_year(year: u32, class: &str) -> Result<()> {
+    create_year_at(&get_git_root()?, year, class)
+}
+
+/// Creates the year structure under an arbitrary repository root.
+pub fn create_year_at(git_root: &str, year: u32, class: &str) -> Result<()> {
     let year_folder = format!("{}/{}/{}", git_root, class, year);
 
-    // Try to load schedule file to get class dates
+    // Load the schedule file to get class dates. A broken schedule (or one naming an unknown
+    // term) is an error rather than a reason to create a note for every day
     let schedule_path = format!("{}/journal-automation/schedules/{}.json", git_root, class);
-    let class_dates: HashSet<NaiveDate> =
-        if let Ok(schedule) = ClassSchedule::from_file(&schedule_path) {
-            schedule.get_class_dates()?.into_iter().collect()
-        } else {
-            // If no schedule file exists, include all dates
-            HashSet::new() // Empty set means create all days
-        };
+    let class_dates: HashSet<NaiveDate> = if std::path::Path::new(&schedule_path).exists() {
+        ClassSchedule::from_file(&schedule_path)?
+            .get_class_dates()?
+            .into_iter()
+            .collect()
+    } else {
+        // If no schedule file exists, include all dates
+        HashSet::new() // Empty set means create all days
+    };
 
     // Create year folder and journey file (only for journal)
     fs::create_dir_all(&year_folder)?;
@@ -151,8 +200,7 @@ pub fn create_year(year: u32, class: &str) -> Result<()> {
             .ok_or_else(|| anyhow::anyhow!("Invalid date"))?;
 
         let month_name = date.format("%B").to_string();
-        let month_name_short = date.format("%b").to_string().to_lowercase();
-        let month_folder = format!("{}/{:02}-{}", year_folder, month, month_name_short);
+        let month_path = format!("{}/{}/{}", git_root, class, month_folder(date));
 
         // Check if there are any days in this month that need files
         let mut has_files = false;
@@ -181,15 +229,15 @@ pub fn create_year(year: u32, class: &str) -> Result<()> {
         // Only create month structure if there are files to create
         if has_files {
             // Create month folder
-            fs::create_dir_all(&month_folder)?;
+            fs::create_dir_all(&month_path)?;
 
             // Create monthly files only for journal
             if class == "journal" {
                 File::create(format!(
                     "{}/{} {} Happenings.md",
-                    month_folder, month_name, year
+                    month_path, month_name, year
                 ))?;
-                File::create(format!("{}/{} goals.md", month_folder, month_name))?;
+                File::create(format!("{}/{} goals.md", month_path, month_name))?;
             }
 
             // Create daily files
@@ -199,9 +247,7 @@ pub fn create_year(year: u32, class: &str) -> Result<()> {
 
                 // Only create file if no schedule exists or if this is a class day
                 if class_dates.is_empty() || class_dates.contains(&date) {
-                    let weekday = date.format("%A").to_string();
-                    let day_file = format!("{}/{:02}_{}.md", month_folder, day, weekday);
-                    File::create(day_file)?;
+                    File::create(format!("{}/{}", month_path, entry_file_name(date)))?;
                 }
             }
         }
@@ -211,62 +257,19 @@ pub fn create_year(year: u32, class: &str) -> Result<()> {
     Ok(())
 }
 
-pub fn find_empty_day(year_filter: Option<u32>) -> Result<()> {
-    let git_root = get_git_root()?;
-    let journal_path = format!("{}/journal", git_root);
+pub fn find_empty_day(year_filter: Option<u32>, notebooks: &[String]) -> Result<()> {
     let current_year = Local::now().year();
     let mut empty_files = Vec::new();
 
-    // Walk through the directory
-    for entry in walkdir::WalkDir::new(&journal_path)
-        .into_iter()
-        .filter_map(|e| e.ok())
-    {
-        let path = entry.path();
-        if path.is_file() && path.extension().map_or(false, |ext| ext == "md") {
-            // Check if file is empty
-            if fs::metadata(path)?.len() == 0 {
-                // Check if path contains a year and month pattern
-                let path_str = path.to_string_lossy();
-                if path_str.contains(|c: char| c.is_ascii_digit()) {
-                    if let Some(year_str) = path_str
-                        .split('/')
-                        .find(|s| s.len() == 4 && s.chars().all(|c| c.is_ascii_digit()))
-                    {
-                        if let Ok(year) = year_str.parse::<i32>() {
-                            // Check year filter if provided
-                            if let Some(filter_year) = year_filter {
-                                if year != filter_year as i32 {
-                                    continue;
-                                }
-                            } else if year > current_year {
-                                // If no filter, skip future years
-                                continue;
-                            }
-
-                            // Must be DD_Weekday.md format and be in a month folder
-                            let file_name = path
-                                .file_name()
-                                .and_then(|f| f.to_str())
-                                .ok_or_else(|| anyhow::anyhow!("Invalid file name"))?;
-
-                            if path_str.contains(|c: char| c == '-') && // Must be in a month folder (contains hyphen)
-                               file_name.len() >= 10 && // At least 2 digits + underscore + weekday + .md
-                               file_name[..2].chars().all(|c| c.is_ascii_digit()) && // First 2 chars are digits
-                               file_name[2..3] == *"_" && // Followed by underscore
-                               file_name[3..].ends_with(".md")
-                            // Ends with .md
-                            {
-                                // Verify it's a valid day number (01-31)
-                                if let Ok(day) = file_name[..2].parse::<u32>() {
-                                    if day >= 1 && day <= 31 {
-                                        empty_files.push(path.to_path_buf());
-                                    }
-                                }
-                            }
-                        }
-                    }
-                }
+    for notebook in notebooks {
+        for file in walk_notebook(notebook)? {
+            // Without a year filter, skip future years
+            let in_range = match year_filter {
+                Some(year) => file.year == year as i32,
+                None => file.year <= current_year,
+            };
+            if in_range && file.date().is_some() && fs::metadata(&file.path)?.len() == 0 {
+                empty_files.push(file);
             }
         }
     }
@@ -275,185 +278,129 @@ pub fn find_empty_day(year_filter: Option<u32>) -> Result<()> {
         if let Some(year) = year_filter {
             anyhow::bail!("No empty files found for year {}", year);
         } else {
-            anyhow::bail!("No empty files found in journal directory");
+            anyhow::bail!("No empty files found in {}", notebooks.join(", "));
         }
     }
 
     // Select a random file
-    let random_file = empty_files
-        .choose(&mut rand::thread_rng())
-        .ok_or_else(|| anyhow::anyhow!("Failed to select random file"))?;
-
-    // Extract date from path
-    let date = extract_date_from_path(random_file)?;
+    let candidates: Vec<Candidate<JournalFile>> =
+        empty_files.into_iter().map(Candidate::new).collect();
+    let random_file = pick_spaced(
+        &candidates,
+        Local::now().date_naive(),
+        0,
+        &mut rand::thread_rng(),
+    )
+    .ok_or_else(|| anyhow::anyhow!("Failed to select random file"))?;
+
+    // Empty files were only collected for valid dates
+    let date = random_file
+        .date()
+        .ok_or_else(|| anyhow::anyhow!("Invalid date"))?;
 
     // Add header to the file
-    let mut file = OpenOptions::new()
-        .write(true)
-        .append(true)
-        .open(random_file)?;
-
-    let current_date = Local::now().format("%m/%d/%Y").to_string();
-    writeln!(
-        file,
-        "# {}\n\n> Transcribed on: {}\n",
-        date.format("%A, %B %d, %Y"),
-        current_date
-    )?;
+    add_transcribed_header(&random_file.path, date)?;
 
     println!(
-        "Randomly selected empty journal entry: {}",
+        "Randomly selected empty {} entry: {}",
+        random_file.notebook,
         date.format("%A, %B %d, %Y")
     );
 
     // Open the file in the editor
-    open_in_editor(random_file.to_str().unwrap())?;
+    open_in_editor(&random_file.path.to_string_lossy())?;
 
     Ok(())
 }
 
-fn extract_date_from_path(path: &PathBuf) -> Result<NaiveDate> {
-    let path_str = path.to_string_lossy();
-    let parts: Vec<&str> = path_str.split('/').collect();
+/// Writes the date header and `> Transcribed on:` marker used for entries copied from paper.
+pub fn add_transcribed_header(path: &std::path::Path, date: NaiveDate) -> Result<()> {
+    let mut file = OpenOptions::new().append(true).open(path)?;
 
-    // Find year, month, and day from path
-    let year = parts
-        .iter()
-        .find(|s| s.len() == 4 && s.chars().all(|c| c.is_ascii_digit()))
-        .ok_or_else(|| anyhow::anyhow!("Could not find year in path"))?
-        .parse::<i32>()?;
+    let current_date = Local::now().format("%m/%d/%Y").to_string();
+    writeln!(
+        file,
+        "# {}\n\n> Transcribed on: {}\n",
+        date.format("%A, %B %d, %Y"),
+        current_date
+    )?;
 
-    let month_part = parts
-        .iter()
-        .find(|s| s.contains('-'))
-        .ok_or_else(|| anyhow::anyhow!("Could not find month in path"))?;
-
-    let month = month_part
-        .split('-')
-        .next()
-        .ok_or_else(|| anyhow::anyhow!("Could not parse month"))?
-        .parse::<u32>()?;
-
-    let day_part = parts
-        .last()
-        .ok_or_else(|| anyhow::anyhow!("Could not find day in path"))?
-        .split('_')
-        .next()
-        .ok_or_else(|| anyhow::anyhow!("Could not parse day"))?
-        .parse::<u32>()?;
-
-    NaiveDate::from_ymd_opt(year, month, day_part).ok_or_else(|| anyhow::anyhow!("Invalid date"))
+    Ok(())
 }
 
-#[derive(Debug)]
-struct YearStats {
-    total_days: u32,
-    empty_days: u32,
+#[derive(Debug, Default)]
+pub struct YearStats {
+    pub total_days: u32,
+    pub empty_days: u32,
 }
 
-#[derive(Debug)]
-struct JournalFile {
-    path: PathBuf,
-    year: i32,
-    month: u32,
-    day: u32,
-    weekday: String,
+impl YearStats {
+    pub fn completion_rate(&self) -> f64 {
+        if self.total_days > 0 {
+            ((self.total_days - self.empty_days) as f64 / self.total_days as f64) * 100.0
+        } else {
+            0.0
+        }
+    }
 }
 
-fn process_journal_files() -> Result<Vec<JournalFile>> {
-    let git_root = get_git_root()?;
-    let journal_path = format!("{}/journal", git_root);
-    let current_year = Local::now().year();
-    let mut files = Vec::new();
-
-    // Walk through the directory
-    for entry in walkdir::WalkDir::new(&journal_path)
-        .into_iter()
-        .filter_map(|e| e.ok())
-    {
-        let path = entry.path();
-        if path.is_file() && path.extension().map_or(false, |ext| ext == "md") {
-            let path_str = path.to_string_lossy();
-
-            // Only process files that match the daily journal format: YYYY/MM-monthname/DD_Weekday.md
-            if let Some(year_str) = path_str
-                .split('/')
-                .find(|s| s.len() == 4 && s.chars().all(|c| c.is_ascii_digit()))
-            {
-                if let Ok(year) = year_str.parse::<i32>() {
-                    if year <= current_year {
-                        // Check if this is a daily journal file (DD_Weekday.md)
-                        let file_name = path
-                            .file_name()
-                            .and_then(|f| f.to_str())
-                            .ok_or_else(|| anyhow::anyhow!("Invalid file name"))?;
-
-                        // Must be DD_Weekday.md format and be in a month folder
-                        if path_str.contains(|c: char| c == '-') && // Must be in a month folder (contains hyphen)
-                           file_name.len() >= 10 && // At least 2 digits + underscore + weekday + .md
-                           file_name[..2].chars().all(|c| c.is_ascii_digit()) && // First 2 chars are digits
-                           file_name[2..3] == *"_" && // Followed by underscore
-                           file_name[3..].ends_with(".md")
-                        // Ends with .md
-                        {
-                            // Verify it's a valid day number (01-31)
-                            if let Ok(day) = file_name[..2].parse::<u32>() {
-                                if day >= 1 && day <= 31 {
-                                    // Extract month from path
-                                    let month_part =
-                                        path_str.split('/').find(|s| s.contains('-')).ok_or_else(
-                                            || anyhow::anyhow!("Could not find month in path"),
-                                        )?;
-
-                                    let month = month_part
-                                        .split('-')
-                                        .next()
-                                        .ok_or_else(|| anyhow::anyhow!("Could not parse month"))?
-                                        .parse::<u32>()?;
-
-                                    // Get the weekday from the filename
-                                    let weekday = file_name[3..file_name.len() - 3].to_string();
-
-                                    files.push(JournalFile {
-                                        path: path.to_path_buf(),
-                                        year,
-                                        month,
-                                        day,
-                                        weekday,
-                                    });
-                                }
-                            }
-                        }
-                    }
-                }
-            }
+/// Counts the days and the empty days of each year.
+pub fn completion_by_year(entries: &[(JournalFile, EntryInfo)]) -> BTreeMap<i32, YearStats> {
+    let mut year_stats: BTreeMap<i32, YearStats> = BTreeMap::new();
+    for (file, info) in entries {
+        let stats = year_stats.entry(file.year).or_default();
+        stats.total_days += 1;
+        if info.size == 0 {
+            stats.empty_days += 1;
         }
     }
-
-    Ok(files)
+    year_stats
 }
 
-pub fn analyze_completion() -> Result<()> {
-    let files = process_journal_files()?;
-    let mut year_stats: HashMap<i32, YearStats> = HashMap::new();
+/// Daily entries in a notebook, skipping years that haven't started.
+pub fn process_journal_files(notebook: &str) -> Result<Vec<JournalFile>> {
+    let current_year = Local::now().year();
+    Ok(walk_notebook(notebook)?
+        .into_iter()
+        .filter(|f| f.year <= current_year)
+        .collect())
+}
 
-    for file in files {
-        let stats = year_stats.entry(file.year).or_insert(YearStats {
-            total_days: 0,
-            empty_days: 0,
-        });
+/// Like `process_journal_files`, with each entry's cached size, word counts and header.
+pub fn indexed_journal_files(notebook: &str) -> Result<Vec<(JournalFile, EntryInfo)>> {
+    let current_year = Local::now().year();
+    Ok(indexed_entries(notebook)?
+        .into_iter()
+        .filter(|(f, _)| f.year <= current_year)
+        .collect())
+}
 
-        stats.total_days += 1;
-        if fs::metadata(&file.path)?.len() == 0 {
-            stats.empty_days += 1;
-        }
+/// Report title with the notebook name for anything other than the daily journal.
+fn report_title(title: &str, notebook: &str) -> String {
+    if notebook == "journal" {
+        title.to_string()
+    } else {
+        format!("{} ({})", title, notebook)
     }
+}
 
-    // Calculate completion rates and sort years
-    let mut years: Vec<_> = year_stats.keys().collect();
-    years.sort();
+pub fn analyze_completion(notebook: &str) -> Result<()> {
+    let entries = indexed_journal_files(notebook)?;
+    let year_stats = completion_by_year(&entries);
+    let current_year = Local::now().year();
+    let this_year: BTreeMap<NaiveDate, f64> = entries
+        .iter()
+        .filter(|(file, _)| file.year == current_year)
+        .filter_map(|(file, info)| {
+            let written = if info.size > 0 { 1.0 } else { 0.0 };
+            file.date().map(|date| (date, written))
+        })
+        .collect();
 
-    println!("\nJournal Completion Analysis");
+    println!(
+        "\n{}",
+        report_title("Journal Completion Analysis", notebook)
+    );
     println!("=========================");
     println!();
 
@@ -465,18 +412,11 @@ pub fn analyze_completion() -> Result<()> {
         .unwrap_or(1);
 
     // First pass: show detailed stats for each year
-    for &year in &years {
-        let stats = &year_stats[&year];
+    for (year, stats) in &year_stats {
         let completed_days = stats.total_days - stats.empty_days;
-        let completion_rate = if stats.total_days > 0 {
-            ((stats.total_days - stats.empty_days) as f64 / stats.total_days as f64) * 100.0
-        } else {
-            0.0
-        };
+        let completion_rate = stats.completion_rate();
 
-        // Create ASCII bar chart (20 characters wide)
-        let bar_length = (completion_rate / 5.0).round() as usize; // 5% per character
-        let bar = "█".repeat(bar_length) + &"░".repeat(20 - bar_length);
+        let bar = chart::bar(completion_rate, 20);
 
         // For current year, include remaining days
         if *year == Local::now().year() {
@@ -513,12 +453,20 @@ pub fn analyze_completion() -> Result<()> {
         }
     }
 
+    // Written (█) and empty (·) days of the current year
+    if !this_year.is_empty() {
+        println!();
+        for line in chart::heatmap(&this_year) {
+            println!("{}", line);
+        }
+    }
+
     println!();
     Ok(())
 }
 
-pub fn validate_structure() -> Result<()> {
-    let files = process_journal_files()?;
+pub fn validate_structure(notebook: &str) -> Result<()> {
+    let files = process_journal_files(notebook)?;
     let mut year_stats: HashMap<i32, HashMap<String, Vec<String>>> = HashMap::new();
     let mut seen_dates: HashMap<String, String> = HashMap::new();
     let mut fixed_capitalization = false;
@@ -574,7 +522,10 @@ pub fn validate_structure() -> Result<()> {
     let mut years: Vec<_> = year_stats.keys().collect();
     years.sort();
 
-    println!("\nJournal Structure Validation");
+    println!(
+        "\n{}",
+        report_title("Journal Structure Validation", notebook)
+    );
     println!("===================================");
 
     if fixed_capitalization {
@@ -865,8 +816,8 @@ fn validate_header(header: &str, file: &JournalFile) -> Vec<String> {
     issues
 }
 
-fn validate_nav(_contents: &str, _file: &JournalFile) -> Vec<String> {
-    // TODO: Implement navigation validation
+fn validate_nav(_info: &EntryInfo, _file: &JournalFile) -> Vec<String> {
+    // TODO: Implement navigation validation from the entry's links (`info.links`)
     // This should check for:
     // - Previous/Next day links
     // - Month/Year navigation
@@ -874,8 +825,8 @@ fn validate_nav(_contents: &str, _file: &JournalFile) -> Vec<String> {
     Vec::new()
 }
 
-fn validate_links(_contents: &str, _file: &JournalFile) -> Vec<String> {
-    // TODO: Implement link validation
+fn validate_links(_info: &EntryInfo, _file: &JournalFile) -> Vec<String> {
+    // TODO: Implement link validation from the entry's links (`info.links`)
     // This should check for:
     // - Broken internal links to other journal entries
     // - Malformed markdown links
@@ -883,31 +834,24 @@ fn validate_links(_contents: &str, _file: &JournalFile) -> Vec<String> {
     Vec::new()
 }
 
-pub fn validate_contents() -> Result<()> {
-    let files = process_journal_files()?;
+pub fn validate_contents(notebook: &str) -> Result<()> {
+    let entries = indexed_journal_files(notebook)?;
     let mut year_stats: HashMap<i32, HashMap<String, Vec<ValidationResult>>> = HashMap::new();
     let mut has_issues = false;
 
-    for file in files {
-        // Guard: Skip empty files
-        let contents = match fs::read_to_string(&file.path) {
-            Ok(content) if content.is_empty() => continue,
-            Ok(content) => content,
-            Err(_) => continue,
-        };
-
-        // Extract header
-        let header = match contents.lines().find(|line| line.starts_with("# ")) {
-            Some(h) => h.trim_start_matches("# ").trim(),
-            None => continue,
+    for (file, info) in entries {
+        // Guard: Skip empty files and files without a header
+        let header = match &info.header {
+            Some(h) if info.size > 0 => h,
+            _ => continue,
         };
 
         let mut validation = ValidationResult::new();
 
         // Perform all validations
         validation.header_issues = validate_header(header, &file);
-        validation.nav_issues = validate_nav(&contents, &file);
-        validation.link_issues = validate_links(&contents, &file);
+        validation.nav_issues = validate_nav(&info, &file);
+        validation.link_issues = validate_links(&info, &file);
 
         // Record issues if any found
         if validation.has_issues() {
@@ -919,7 +863,7 @@ pub fn validate_contents() -> Result<()> {
         }
     }
 
-    println!("\nJournal Content Validation");
+    println!("\n{}", report_title("Journal Content Validation", notebook));
     println!("===================================");
 
     if !has_issues {
@@ -964,146 +908,350 @@ pub fn validate_contents() -> Result<()> {
     Ok(())
 }
 
-pub fn analyze_length() -> Result<()> {
-    let files = process_journal_files()?;
-    let mut year_stats: HashMap<i32, (u64, u64, u32)> = HashMap::new(); // (total_words, total_lines, entry_count)
+/// How `analyze-length` groups entries.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum LengthGrouping {
+    Year,
+    Month,
+    Week,
+    Weekday,
+}
 
-    for file in files {
-        // Skip empty files
-        let contents = match fs::read_to_string(&file.path) {
-            Ok(content) if content.is_empty() => continue,
-            Ok(content) => content,
-            Err(_) => continue,
-        };
+pub fn analyze_length(notebook: &str, by: LengthGrouping) -> Result<()> {
+    // Empty files are placeholders, not short entries
+    let entries: Vec<(JournalFile, EntryInfo)> = indexed_journal_files(notebook)?
+        .into_iter()
+        .filter(|(_, info)| info.size > 0)
+        .collect();
 
-        let line_count = contents.lines().count() as u64;
-        let word_count = contents.split_whitespace().count() as u64;
+    println!("\n{}", report_title("Journal Length Analysis", notebook));
+    println!("===================================");
 
+    match by {
+        LengthGrouping::Year => print_length_by_year(&entries),
+        _ => print_length_table(&entries, by),
+    }
+    print_length_extremes(&entries);
+
+    Ok(())
+}
+
+fn print_length_by_year(entries: &[(JournalFile, EntryInfo)]) {
+    let mut year_stats: HashMap<i32, (u64, u64, u32)> = HashMap::new(); // (total_words, total_lines, entry_count)
+    let mut year_words: HashMap<i32, Vec<u64>> = HashMap::new();
+
+    for (file, info) in entries {
         let stats = year_stats.entry(file.year).or_insert((0, 0, 0));
-        stats.0 += word_count;
-        stats.1 += line_count;
+        stats.0 += info.words;
+        stats.1 += info.lines;
         stats.2 += 1;
+        year_words.entry(file.year).or_default().push(info.words);
     }
 
-    println!("\nJournal Length Analysis");
-    println!("===================================");
-
     // Sort years for consistent output
     let mut years: Vec<_> = year_stats.keys().collect();
     years.sort();
 
     // Calculate averages and find maximums
     let mut avg_stats: Vec<(i32, f64, f64)> = Vec::new(); // (year, avg_words, avg_lines)
-    let mut max_avg_words: f64 = 0.0;
-    let mut max_avg_lines: f64 = 0.0;
 
     for &year in &years {
         let (total_words, total_lines, entry_count) = year_stats[&year];
         if entry_count > 0 {
             let avg_words = total_words as f64 / entry_count as f64;
             let avg_lines = total_lines as f64 / entry_count as f64;
-            max_avg_words = f64::max(max_avg_words, avg_words);
-            max_avg_lines = f64::max(max_avg_lines, avg_lines);
             avg_stats.push((*year, avg_words, avg_lines));
 
             println!("\n{}:", year);
             println!("  Entries: {}", entry_count);
+            let words = year_words.get_mut(year).unwrap();
+            words.sort_unstable();
             println!("  Average Words: {:.1}", avg_words);
+            println!("  Median Words: {:.1}", median(words));
+            println!(
+                "  Words (10th/90th percentile): {}/{}",
+                percentile(words, 10.0),
+                percentile(words, 90.0)
+            );
             println!("  Average Lines: {:.1}", avg_lines);
             println!("  Total Words: {}", total_words);
             println!("  Total Lines: {}", total_lines);
         }
     }
 
-    // Print word count chart
+    let words_by_year: Vec<(String, f64)> = avg_stats
+        .iter()
+        .map(|(year, avg_words, _)| (year.to_string(), *avg_words))
+        .collect();
+    let lines_by_year: Vec<(String, f64)> = avg_stats
+        .iter()
+        .map(|(year, _, avg_lines)| (year.to_string(), *avg_lines))
+        .collect();
+
     println!("\nAverage Words Per Entry");
     println!("===================================");
+    for line in chart::bar_chart(&words_by_year, 1) {
+        println!("{}", line);
+    }
 
-    // Calculate scale for words (round up to nearest 100 for cleaner numbers)
-    let word_scale = ((max_avg_words / 100.0).ceil() * 100.0) / 10.0;
-    let word_percentages = (0..=10)
-        .map(|i| word_scale * (10 - i) as f64)
-        .collect::<Vec<_>>();
-
-    // Print y-axis labels and bars for words
-    for &value in &word_percentages {
-        let mut line = format!("{:5.0} │", value);
-        for &(_, avg_words, _) in &avg_stats {
-            if avg_words >= value {
-                line.push('█');
-            } else {
-                line.push(' ');
-            }
-            line.push(' ');
-        }
+    println!("\nAverage Lines Per Entry");
+    println!("===================================");
+    for line in chart::bar_chart(&lines_by_year, 1) {
         println!("{}", line);
     }
+}
 
-    // Print x-axis
-    let axis_length = avg_stats.len() * 2;
-    println!("      └{}", "─".repeat(axis_length));
+/// One row per month, ISO week or weekday with averages, median and spread of word counts.
+fn print_length_table(entries: &[(JournalFile, EntryInfo)], by: LengthGrouping) {
+    // Keys sort chronologically (or Monday first for weekdays)
+    let mut groups: BTreeMap<(u32, String), Vec<(u64, u64)>> = BTreeMap::new();
+    for (file, info) in entries {
+        let Some(date) = file.date() else { continue };
+        let key = match by {
+            LengthGrouping::Month => (0, date.format("%Y-%m").to_string()),
+            LengthGrouping::Week => (0, date.format("%G-W%V").to_string()),
+            LengthGrouping::Weekday => (
+                date.weekday().num_days_from_monday(),
+                date.format("%A").to_string(),
+            ),
+            LengthGrouping::Year => (0, date.format("%Y").to_string()),
+        };
+        groups
+            .entry(key)
+            .or_default()
+            .push((info.words, info.lines));
+    }
 
-    // Print year labels vertically
-    for digit in 0..4 {
-        let mut line = String::from("       ");
-        for &(year, _, _) in &avg_stats {
-            let year_str = year.to_string();
-            if digit < year_str.len() {
-                line.push(year_str.chars().nth(digit).unwrap());
-            } else {
-                line.push(' ');
-            }
-            line.push(' ');
+    let rows: Vec<(String, Vec<u64>, f64, f64)> = groups
+        .into_iter()
+        .map(|((_, label), lengths)| {
+            let mut words: Vec<u64> = lengths.iter().map(|(w, _)| *w).collect();
+            words.sort_unstable();
+            let count = lengths.len() as f64;
+            let avg_words = words.iter().sum::<u64>() as f64 / count;
+            let avg_lines = lengths.iter().map(|(_, l)| *l).sum::<u64>() as f64 / count;
+            (label, words, avg_words, avg_lines)
+        })
+        .collect();
+    let max_avg_words = rows.iter().map(|r| r.2).fold(0.0, f64::max);
+    let label_width = rows.iter().map(|r| r.0.len()).max().unwrap_or(4);
+
+    println!();
+    for (label, words, avg_words, avg_lines) in &rows {
+        // Bars are relative to the longest average
+        let bar = if max_avg_words > 0.0 {
+            chart::bar(avg_words / max_avg_words * 100.0, 20)
+        } else {
+            chart::bar(0.0, 20)
+        };
+        println!(
+            "{:<label_width$} | {:>4} entries | avg {:>7.1} | median {:>7.1} | p10 {:>5} | p90 {:>5} | lines {:>5.1} {}",
+            label,
+            words.len(),
+            avg_words,
+            median(words),
+            percentile(words, 10.0),
+            percentile(words, 90.0),
+            avg_lines,
+            bar,
+            label_width = label_width
+        );
+    }
+
+    if matches!(by, LengthGrouping::Month | LengthGrouping::Week) {
+        let averages: Vec<(String, f64)> = rows
+            .iter()
+            .map(|(label, _, avg, _)| (label.clone(), *avg))
+            .collect();
+        println!("\nAverage Words Over Time");
+        println!("===================================");
+        for line in chart::line_chart(&averages, 8) {
+            println!("{}", line);
         }
-        println!("{}", line);
     }
+}
 
-    // Print line count chart
-    println!("\nAverage Lines Per Entry");
+/// Lists the longest and shortest entries and the overall trend in entry length.
+fn print_length_extremes(entries: &[(JournalFile, EntryInfo)]) {
+    let mut dated: Vec<(NaiveDate, u64)> = entries
+        .iter()
+        .filter_map(|(file, info)| file.date().map(|date| (date, info.words)))
+        .collect();
+    if dated.is_empty() {
+        println!("\nNo entries with content.");
+        return;
+    }
+    dated.sort_by_key(|(date, words)| (std::cmp::Reverse(*words), *date));
+
+    let show = |(date, words): &(NaiveDate, u64)| {
+        println!("  {:>6} words  {}", words, date.format("%A, %B %d, %Y"));
+    };
+    println!("\nLongest Entries");
     println!("===================================");
+    dated.iter().take(5).for_each(show);
+    println!("\nShortest Entries");
+    println!("===================================");
+    dated.iter().rev().take(5).for_each(show);
+
+    let points: Vec<(NaiveDate, f64)> = dated.iter().map(|(d, w)| (*d, *w as f64)).collect();
+    if let Some(slope) = trend_per_year(&points) {
+        let direction = if slope > 0.0 { "longer" } else { "shorter" };
+        println!(
+            "\nTrend: {:+.1} words per entry per year (entries are getting {})",
+            slope, direction
+        );
+
+        let mut months: BTreeMap<String, (u64, u64)> = BTreeMap::new();
+        for (date, words) in &dated {
+            let month = months.entry(date.format("%Y-%m").to_string()).or_default();
+            month.0 += words;
+            month.1 += 1;
+        }
+        let averages: Vec<f64> = months
+            .values()
+            .map(|(words, count)| *words as f64 / *count as f64)
+            .collect();
+        let first = months.keys().next().map_or("", |m| m.as_str());
+        let last = months.keys().last().map_or("", |m| m.as_str());
+        println!(
+            "Monthly average: {} {} {}",
+            first,
+            chart::sparkline(&averages),
+            last
+        );
+    }
+}
 
-    // Calculate scale for lines (round up to nearest 10 for cleaner numbers)
-    let line_scale = ((max_avg_lines / 10.0).ceil() * 10.0) / 10.0;
-    let line_percentages = (0..=10)
-        .map(|i| line_scale * (10 - i) as f64)
-        .collect::<Vec<_>>();
-
-    // Print y-axis labels and bars for lines
-    for &value in &line_percentages {
-        let mut line = format!("{:5.0} │", value);
-        for &(_, _, avg_lines) in &avg_stats {
-            if avg_lines >= value {
-                line.push('█');
-            } else {
-                line.push(' ');
-            }
-            line.push(' ');
+/// A class's days so far and the notes written for them.
+#[derive(Debug, Default, PartialEq)]
+pub struct ClassAttendance {
+    pub held: Vec<NaiveDate>,
+    pub missed: Vec<NaiveDate>,
+    pub upcoming: usize,
+    pub words: usize,
+}
+
+impl ClassAttendance {
+    pub fn attended(&self) -> usize {
+        self.held.len() - self.missed.len()
+    }
+
+    pub fn completion_rate(&self) -> f64 {
+        if self.held.is_empty() {
+            0.0
+        } else {
+            (self.attended() as f64 / self.held.len() as f64) * 100.0
         }
-        println!("{}", line);
     }
+}
 
-    // Print x-axis
-    println!("      └{}", "─".repeat(axis_length));
+/// Compares the class days held up to `today` with their notes. `notes` returns a day's notes,
+/// empty when there are none; a day with only whitespace counts as missed.
+pub fn class_attendance(
+    schedule: &ClassSchedule,
+    today: NaiveDate,
+    notes: impl Fn(NaiveDate) -> Result<String>,
+) -> Result<ClassAttendance> {
+    let mut attendance = ClassAttendance::default();
+    for date in schedule.get_class_dates()? {
+        if date > today {
+            attendance.upcoming += 1;
+            continue;
+        }
+        attendance.held.push(date);
+        let contents = notes(date)?;
+        if contents.trim().is_empty() {
+            attendance.missed.push(date);
+        } else {
+            attendance.words += contents.split_whitespace().count();
+        }
+    }
+    Ok(attendance)
+}
 
-    // Print year labels vertically
-    for digit in 0..4 {
-        let mut line = String::from("       ");
-        for &(year, _, _) in &avg_stats {
-            let year_str = year.to_string();
-            if digit < year_str.len() {
-                line.push(year_str.chars().nth(digit).unwrap());
-            } else {
-                line.push(' ');
+/// A class's notes for a date, empty when the file doesn't exist.
+pub fn read_class_notes(date: NaiveDate, class: &str) -> Result<String> {
+    let path = get_journal_path_for_date(date, class)?;
+    match fs::read_to_string(&path) {
+        Ok(contents) => Ok(contents),
+        Err(e) if e.kind() == ErrorKind::NotFound => Ok(String::new()),
+        Err(e) => Err(e).with_context(|| format!("Failed to read {}", path)),
+    }
+}
+
+pub fn analyze_classes() -> Result<()> {
+    let today = Local::now().date_naive();
+
+    println!("\nClass Notes Analysis");
+    println!("===================================");
+
+    let mut analyzed = 0;
+    for path in list_schedule_files()? {
+        let schedule = match ClassSchedule::from_file(&path.to_string_lossy()) {
+            Ok(schedule) => schedule,
+            Err(e) => {
+                println!("\nSkipping {}: {:#}", path.display(), e);
+                continue;
+            }
+        };
+        analyzed += 1;
+
+        let attendance = class_attendance(&schedule, today, |date| {
+            read_class_notes(date, &schedule.class_name)
+        })?;
+        let attended = attendance.attended();
+        let avg_words = if attended == 0 {
+            0.0
+        } else {
+            attendance.words as f64 / attended as f64
+        };
+
+        let bar = chart::bar(attendance.completion_rate(), 20);
+        let completion_marker = if attendance.missed.is_empty() && !attendance.held.is_empty() {
+            " ✓"
+        } else {
+            ""
+        };
+
+        println!("\n{}:", schedule.class_name);
+        println!(
+            "  Notes: {}/{} | {:>6.1}% {}{}",
+            attended,
+            attendance.held.len(),
+            attendance.completion_rate(),
+            bar,
+            completion_marker
+        );
+        println!("  Average Words: {:.1}", avg_words);
+        if !attendance.missed.is_empty() {
+            println!("  Missed Sessions:");
+            for dates in attendance.missed.chunks(6) {
+                let dates: Vec<String> = dates
+                    .iter()
+                    .map(|d| d.format("%Y-%m-%d").to_string())
+                    .collect();
+                println!("    {}", dates.join(", "));
             }
-            line.push(' ');
         }
-        println!("{}", line);
     }
 
+    if analyzed == 0 {
+        println!("\nNo class schedules found.");
+    }
+    println!();
     Ok(())
 }
 
 pub fn add_custom_header(header: &str) -> Result<()> {
+    let journal_path = append_to_todays_entry(&format!("## {}", header))?;
+
+    // Open the file in the editor
+    open_in_editor(&journal_path)
+}
+
+/// Appends text to today's journal entry, creating the standard header first if the file is empty
+/// (without asking for mood and energy). Returns the path of the entry.
+pub fn append_to_todays_entry(text: &str) -> Result<String> {
     let journal_path = get_todays_journal_path("journal")?;
     let path = std::path::Path::new(&journal_path);
 
@@ -1112,7 +1260,7 @@ pub fn add_custom_header(header: &str) -> Result<()> {
 
     // If file is empty, create standard header first
     if is_empty {
-        create_journal_entry(&journal_path, "journal")?;
+        create_journal_entry(&journal_path, "journal", false)?;
     }
 
     // Open file for appending
@@ -1121,12 +1269,57 @@ pub fn add_custom_header(header: &str) -> Result<()> {
         .open(path)
         .context("Failed to open journal file")?;
 
-    // Add a newline before the custom header
+    // Add a newline before the new text
     writeln!(file)?;
+    writeln!(file, "{}", text)?;
 
-    // Add the custom header
-    writeln!(file, "## {}", header)?;
+    Ok(journal_path)
+}
 
-    // Open the file in the editor
-    open_in_editor(&journal_path)
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_class_attendance() {
+        let schedule: ClassSchedule = serde_json::from_str(
+            r#"{
+                "class_name": "CS201",
+                "start_date": "2024-03-04",
+                "end_date": "2024-03-15",
+                "schedule": [
+                    { "weekday": "monday", "start_time": "10:00", "end_time": "11:00" },
+                    { "weekday": "wednesday", "start_time": "10:00", "end_time": "11:00" }
+                ],
+                "exclusions": ["2024-03-06"]
+            }"#,
+        )
+        .unwrap();
+        let date = |day| NaiveDate::from_ymd_opt(2024, 3, day).unwrap();
+
+        // Notes on the 4th, whitespace only on the 11th, nothing on the 13th
+        let attendance = class_attendance(&schedule, date(13), |d| {
+            Ok(match d.day() {
+                4 => "Linked lists and arrays".to_string(),
+                11 => " \n".to_string(),
+                _ => String::new(),
+            })
+        })
+        .unwrap();
+        assert_eq!(
+            attendance,
+            ClassAttendance {
+                held: vec![date(4), date(11), date(13)],
+                missed: vec![date(11), date(13)],
+                upcoming: 0,
+                words: 4,
+            }
+        );
+        assert_eq!(attendance.attended(), 1);
+
+        let attendance = class_attendance(&schedule, date(5), |_| Ok(String::new())).unwrap();
+        assert_eq!(attendance.held, vec![date(4)]);
+        assert_eq!(attendance.upcoming, 2);
+        assert_eq!(attendance.completion_rate(), 0.0);
+    }
 }
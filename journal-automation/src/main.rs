@@ -1,19 +1,50 @@
+mod block;
+mod chart;
 mod cli;
+mod export;
+mod goals;
+mod header;
+mod health;
+mod ics;
+mod index;
 mod journal;
+mod month;
+mod mood;
+mod next_class;
+mod notebook;
+mod obsidian;
+mod paper;
+mod report;
+mod review;
+mod runs;
 mod schedule;
+mod schedule_check;
+mod shuffle;
+mod stats;
+mod term;
 mod utils;
+mod video;
 
 use anyhow::Result;
 use clap::Parser;
-use cli::{Cli, Commands};
+use cli::{Cli, Commands, PaperCommands, ScheduleCommands, TermCommands, VideoCommands};
 
 fn main() -> Result<()> {
     let cli = Cli::parse();
 
     match cli.command {
-        Commands::StartJournal { class } => {
+        Commands::StartJournal {
+            class,
+            auto,
+            grace_minutes,
+        } => {
+            let class = if auto {
+                next_class::detect_class(grace_minutes)?
+            } else {
+                class
+            };
             let journal_path = journal::get_todays_journal_path(&class)?;
-            journal::create_journal_entry(&journal_path, &class)?;
+            journal::create_journal_entry(&journal_path, &class, true)?;
         }
         Commands::OpenJournal { class } => {
             journal::open_journal_entry(&class)?;
@@ -24,23 +55,130 @@ fn main() -> Result<()> {
         Commands::CreateYear { year, class } => {
             journal::create_year(year, &class)?;
         }
-        Commands::EmptyDay { year } => {
-            journal::find_empty_day(year)?;
+        Commands::YearReview { year } => {
+            review::year_review(year)?;
+        }
+        Commands::MonthSummary { month } => {
+            month::month_summary(month.unwrap_or_else(utils::current_month))?;
+        }
+        Commands::Goals {
+            month,
+            year,
+            rollover,
+        } => match year {
+            Some(year) => goals::year_goals(year as i32)?,
+            None => {
+                let month = month.unwrap_or_else(utils::current_month);
+                goals::month_goals(month)?;
+                if rollover {
+                    goals::rollover_goals(month)?;
+                }
+            }
+        },
+        Commands::EmptyDay { year, notebooks } => {
+            let notebooks = notebook::select_notebooks(notebooks.class.as_deref(), notebooks.all)?;
+            journal::find_empty_day(year, &notebooks)?;
         }
         Commands::AddCustomHeader { header } => {
             journal::add_custom_header(&header)?;
         }
-        Commands::AnalyzeCompletion => {
-            journal::analyze_completion()?;
+        Commands::Mood { mood, energy } => {
+            mood::set_mood(mood, energy)?;
+        }
+        Commands::AnalyzeCompletion { notebooks } => {
+            for notebook in notebook::select_notebooks(notebooks.class.as_deref(), notebooks.all)? {
+                journal::analyze_completion(&notebook)?;
+            }
+        }
+        Commands::AnalyzeLength { by, notebooks } => {
+            for notebook in notebook::select_notebooks(notebooks.class.as_deref(), notebooks.all)? {
+                journal::analyze_length(&notebook, by)?;
+            }
+        }
+        Commands::AnalyzeMood => {
+            mood::analyze_mood()?;
+        }
+        Commands::AnalyzeClasses => {
+            journal::analyze_classes()?;
+        }
+        Commands::Report {
+            html,
+            year,
+            notebooks,
+        } => {
+            let notebooks = notebook::select_notebooks(notebooks.class.as_deref(), notebooks.all)?;
+            report::html_report(&html, &notebooks, year.map(|y| y as i32))?;
+        }
+        Commands::ValidateStructure { notebooks } => {
+            for notebook in notebook::select_notebooks(notebooks.class.as_deref(), notebooks.all)? {
+                journal::validate_structure(&notebook)?;
+            }
+        }
+        Commands::ValidateContents { notebooks } => {
+            for notebook in notebook::select_notebooks(notebooks.class.as_deref(), notebooks.all)? {
+                journal::validate_contents(&notebook)?;
+            }
+        }
+        Commands::Video { command } => match command {
+            VideoCommands::Next { list, window } => {
+                video::next_video(list.as_deref(), window)?;
+            }
+            VideoCommands::Stats { list } => {
+                video::video_stats(list.as_deref())?;
+            }
+        },
+        Commands::Paper { command } => match command {
+            PaperCommands::Add {
+                name,
+                start_date,
+                end_date,
+            } => {
+                paper::add_volume(&name, &start_date, &end_date)?;
+            }
+            PaperCommands::Status => {
+                paper::paper_status()?;
+            }
+            PaperCommands::Next { pages, volume } => {
+                paper::next_pages(pages, volume.as_deref())?;
+            }
+        },
+        Commands::RolloverDailyNotes => {
+            obsidian::rollover_daily_notes()?;
+        }
+        Commands::ExportTemplate { dest, anonymize } => {
+            export::export_template(&dest, anonymize)?;
+        }
+        Commands::ImportHealth { export } => {
+            health::import_health(&export)?;
         }
-        Commands::AnalyzeLength => {
-            journal::analyze_length()?;
+        Commands::ImportRuns { dir } => {
+            runs::import_runs(&dir)?;
         }
-        Commands::ValidateStructure => {
-            journal::validate_structure()?;
+        Commands::Schedule { command } => match command {
+            ScheduleCommands::Import { file, class, force } => {
+                ics::import_schedule(&file, class.as_deref(), force)?;
+            }
+            ScheduleCommands::Check { travel_minutes } => {
+                schedule_check::check_schedules(travel_minutes)?;
+            }
+            ScheduleCommands::Export { class } => {
+                ics::export_schedule(&class)?;
+            }
+        },
+        Commands::Next { open } => {
+            next_class::next_class(open)?;
         }
-        Commands::ValidateContents => {
-            journal::validate_contents()?;
+        Commands::Term { command } => match command {
+            TermCommands::CreateNotes { name } => {
+                term::create_notes(name.as_deref())?;
+            }
+            TermCommands::Report { name } => {
+                term::term_report(name.as_deref())?;
+            }
+        },
+        Commands::ExportIcs { year, notebooks } => {
+            let notebooks = notebook::select_notebooks(notebooks.class.as_deref(), notebooks.all)?;
+            ics::export_journal_days(year, &notebooks)?;
         }
     }
 
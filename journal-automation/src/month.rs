@@ -0,0 +1,202 @@
+use crate::block::write_block;
+use crate::goals;
+use crate::index::find_tags;
+use crate::journal::get_journal_path_for_date;
+use crate::notebook::month_folder;
+use crate::utils::get_git_root;
+use anyhow::Result;
+use chrono::{Datelike, Duration, NaiveDate};
+use std::{collections::BTreeMap, fs, path::Path};
+
+// H2 sections the tool writes itself, as opposed to ones added with add-custom-header
+const GENERATED_SECTIONS: [&str; 3] = ["Class Information", "Notes", "Video"];
+
+const HIGHLIGHT_TAG: &str = "highlight";
+
+/// What a daily entry contributes to its month.
+#[derive(Debug, Default, PartialEq)]
+struct DaySummary {
+    sections: Vec<(String, String)>, // custom H2 title and its first line
+    highlights: Vec<String>,
+}
+
+fn strip_list_marker(line: &str) -> &str {
+    line.trim().trim_start_matches(['-', '*', '+']).trim_start()
+}
+
+fn summarize_day(contents: &str) -> DaySummary {
+    let mut summary = DaySummary::default();
+    let mut section: Option<(String, Option<String>)> = None;
+    let mut in_block = false;
+    let mut in_fence = false;
+
+    for line in contents.lines() {
+        // Skip the health/runs tables and other generated blocks
+        if line.starts_with("<!-- ja:") {
+            in_block = line.ends_with(":start -->");
+            continue;
+        }
+        if in_block {
+            continue;
+        }
+
+        // Code blocks can hold anything, including `#highlight`
+        if line.trim_start().starts_with("```") || line.trim_start().starts_with("~~~") {
+            in_fence = !in_fence;
+            continue;
+        }
+        if in_fence {
+            continue;
+        }
+
+        let level = line.len() - line.trim_start_matches('#').len();
+        if level > 0 && line[level..].starts_with(' ') {
+            // Only H1 and H2 end a section; smaller headings are part of it
+            if level > 2 {
+                continue;
+            }
+            if let Some((title, first)) = section.take() {
+                summary.sections.push((title, first.unwrap_or_default()));
+            }
+            if let Some(title) = line.strip_prefix("## ") {
+                let title = title.trim();
+                if !GENERATED_SECTIONS.contains(&title) {
+                    section = Some((title.to_string(), None));
+                }
+            }
+            continue;
+        }
+
+        let tags = find_tags(line);
+        if tags.iter().any(|t| t == HIGHLIGHT_TAG) {
+            let text: Vec<&str> = strip_list_marker(line)
+                .split_whitespace()
+                .filter(|word| !word.eq_ignore_ascii_case("#highlight"))
+                .collect();
+            summary.highlights.push(text.join(" "));
+        }
+
+        if let Some((_, first @ None)) = &mut section {
+            let text = strip_list_marker(line);
+            if !text.is_empty() {
+                *first = Some(text.to_string());
+            }
+        }
+    }
+    if let Some((title, first)) = section {
+        summary.sections.push((title, first.unwrap_or_default()));
+    }
+    summary
+}
+
+fn happenings_block(days: &BTreeMap<NaiveDate, DaySummary>) -> String {
+    let link = |date: &NaiveDate| {
+        format!(
+            "[{}]({})",
+            date.format("%b %d"),
+            crate::notebook::entry_file_name(*date)
+        )
+    };
+
+    let mut block = String::from("## From the Daily Entries\n");
+    let sections: Vec<String> = days
+        .iter()
+        .flat_map(|(date, day)| {
+            day.sections.iter().map(move |(title, first)| {
+                if first.is_empty() {
+                    format!("- {} **{}**", link(date), title)
+                } else {
+                    format!("- {} **{}**: {}", link(date), title, first)
+                }
+            })
+        })
+        .collect();
+    let highlights: Vec<String> = days
+        .iter()
+        .flat_map(|(date, day)| {
+            day.highlights
+                .iter()
+                .map(move |text| format!("- {} {}", link(date), text))
+        })
+        .collect();
+
+    if sections.is_empty() && highlights.is_empty() {
+        block.push_str("\nNothing yet.\n");
+    }
+    if !highlights.is_empty() {
+        block.push_str(&format!("\n### Highlights\n\n{}\n", highlights.join("\n")));
+    }
+    if !sections.is_empty() {
+        block.push_str(&format!("\n### Sections\n\n{}\n", sections.join("\n")));
+    }
+    block
+}
+
+/// Collects custom sections and #highlight lines from a month's entries into its Happenings file,
+/// then reports on the month's goals.
+pub fn month_summary(month: NaiveDate) -> Result<()> {
+    let month_name = month.format("%B").to_string();
+    let month_path = Path::new(&get_git_root()?)
+        .join("journal")
+        .join(month_folder(month));
+    if !month_path.exists() {
+        anyhow::bail!(
+            "No journal folder for {} {} (create it with `ja create-year {}`)",
+            month_name,
+            month.year(),
+            month.year()
+        );
+    }
+
+    let mut days = BTreeMap::new();
+    let mut date = month;
+    while date.month() == month.month() {
+        let path = get_journal_path_for_date(date, "journal")?;
+        if let Ok(contents) = fs::read_to_string(&path) {
+            days.insert(date, summarize_day(&contents));
+        }
+        date += Duration::days(1);
+    }
+
+    let happenings_path = month_path.join(format!("{} {} Happenings.md", month_name, month.year()));
+    write_block(
+        &happenings_path.to_string_lossy(),
+        "summary",
+        &happenings_block(&days),
+    )?;
+    let (sections, highlights) = days.values().fold((0, 0), |(s, h), day| {
+        (s + day.sections.len(), h + day.highlights.len())
+    });
+    println!(
+        "Added {} sections and {} highlights to {}",
+        sections,
+        highlights,
+        happenings_path.display()
+    );
+
+    goals::month_goals(month)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_summarize_day() {
+        let contents = "# Tuesday, March 05, 2024\n\n## Notes\nRegular day\n\n## Job interview\n\n- Went well, second round next week\n- More\n\nSaw the aurora #highlight\nFinished the race #goal/Run-a-10k\n## Trip\n### Day one\nDrove north\n```sh\n# not a heading #highlight\n```\n## Empty section\n\n<!-- ja:runs:start -->\n## Fake heading\n<!-- ja:runs:end -->\n";
+        assert_eq!(
+            summarize_day(contents),
+            DaySummary {
+                sections: vec![
+                    (
+                        "Job interview".to_string(),
+                        "Went well, second round next week".to_string()
+                    ),
+                    ("Trip".to_string(), "Drove north".to_string()),
+                    ("Empty section".to_string(), String::new()),
+                ],
+                highlights: vec!["Saw the aurora".to_string()],
+            }
+        );
+    }
+}
@@ -0,0 +1,262 @@
+use crate::chart;
+use crate::header::set_header_field;
+use crate::journal::{get_todays_journal_path, indexed_journal_files};
+use crate::schedule::{get_schedules_dir, load_schedules};
+use crate::stats::correlation;
+use anyhow::{Context, Result};
+use chrono::{Datelike, NaiveDate};
+use std::{
+    collections::{BTreeMap, HashSet},
+    fs,
+    path::Path,
+};
+
+/// Records mood (and optionally energy) in today's header table.
+pub fn set_mood(mood: u8, energy: Option<u8>) -> Result<()> {
+    let path = get_todays_journal_path("journal")?;
+    let contents =
+        fs::read_to_string(&path).with_context(|| format!("Journal file not found: {}", path))?;
+
+    let mut updated = set_header_field(&contents, "mood", &mood.to_string());
+    if let Some(energy) = energy {
+        updated = updated.and_then(|c| set_header_field(&c, "energy", &energy.to_string()));
+    }
+    let Some(updated) = updated else {
+        anyhow::bail!(
+            "Today's entry has no header table yet; run `ja start-journal` first: {}",
+            path
+        );
+    };
+    fs::write(&path, updated)?;
+
+    match energy {
+        Some(energy) => println!("Mood {} and energy {} recorded in {}", mood, energy, path),
+        None => println!("Mood {} recorded in {}", mood, path),
+    }
+    Ok(())
+}
+
+/// A day with a recorded mood and whatever else is known about it.
+struct MoodDay {
+    date: NaiveDate,
+    mood: f64,
+    energy: Option<f64>,
+    high_f: Option<f64>,
+    weather: Option<&'static str>,
+    sleep_hours: Option<f64>,
+}
+
+/// `40-55 F ☀️` to the high temperature and the condition behind the emoji.
+fn parse_weather(text: &str) -> (Option<f64>, Option<&'static str>) {
+    // The low and high are split by the first dash after a digit, so `-12--5` reads as -12 to -5
+    let high = text.split_whitespace().next().and_then(|range| {
+        let (i, _) = range
+            .char_indices()
+            .skip(1)
+            .find(|&(i, c)| c == '-' && range[..i].ends_with(|p: char| p.is_ascii_digit()))?;
+        range[i + 1..].parse().ok()
+    });
+    let condition = match text.split_whitespace().last() {
+        Some("☀️") => Some("Sunny"),
+        Some("⛅") => Some("Partly cloudy"),
+        Some("☁️") => Some("Cloudy"),
+        Some("🌧️") => Some("Rain"),
+        Some("⛈️") => Some("Thunder"),
+        Some("❄️") => Some("Snow"),
+        Some("🌈") => Some("Other"),
+        _ => None,
+    };
+    (high, condition)
+}
+
+/// `7h 05m` to hours.
+fn parse_sleep(text: &str) -> Option<f64> {
+    let (hours, minutes) = text.split_once("h")?;
+    let minutes = minutes.trim().trim_end_matches('m');
+    Some(hours.trim().parse::<f64>().ok()? + minutes.parse::<f64>().unwrap_or(0.0) / 60.0)
+}
+
+fn average(values: impl Iterator<Item = f64>) -> Option<f64> {
+    let (sum, count) = values.fold((0.0, 0), |(s, c), v| (s + v, c + 1));
+    (count > 0).then(|| sum / count as f64)
+}
+
+/// Groups days by a key (sorted by its order) and prints average mood and energy for each.
+fn print_groups<K: Ord>(
+    title: &str,
+    days: &[MoodDay],
+    key: impl Fn(&MoodDay) -> Option<(K, String)>,
+) {
+    let mut groups: BTreeMap<K, (String, Vec<&MoodDay>)> = BTreeMap::new();
+    for day in days {
+        if let Some((order, label)) = key(day) {
+            groups
+                .entry(order)
+                .or_insert((label, Vec::new()))
+                .1
+                .push(day);
+        }
+    }
+    if groups.is_empty() {
+        return;
+    }
+
+    println!("\n{}", title);
+    println!("===================================");
+    let label_width = groups.values().map(|(l, _)| l.len()).max().unwrap_or(0);
+    for (label, days) in groups.values() {
+        let mood = average(days.iter().map(|d| d.mood)).unwrap_or(0.0);
+        let energy = average(days.iter().filter_map(|d| d.energy))
+            .map(|e| format!(" | energy {:.1}", e))
+            .unwrap_or_default();
+        println!(
+            "{:<label_width$} | {:>3} days | mood {:.1} {}{}",
+            label,
+            days.len(),
+            mood,
+            chart::bar((mood - 1.0) / 4.0 * 100.0, 20),
+            energy,
+            label_width = label_width
+        );
+    }
+}
+
+fn print_correlation(label: &str, pairs: &[(f64, f64)]) {
+    if let Some(r) = correlation(pairs) {
+        let relationship = match r.abs() {
+            a if a < 0.1 => "no relationship",
+            a if a < 0.3 && r < 0.0 => "weak inverse relationship",
+            a if a < 0.3 => "weak relationship",
+            a if a < 0.5 && r < 0.0 => "moderate inverse relationship",
+            a if a < 0.5 => "moderate relationship",
+            _ if r < 0.0 => "strong inverse relationship",
+            _ => "strong relationship",
+        };
+        println!(
+            "{}: r = {:+.2} ({}, {} days)",
+            label,
+            r,
+            relationship,
+            pairs.len()
+        );
+    }
+}
+
+/// Relates recorded moods to the weekday, weather, sleep and class days.
+pub fn analyze_mood() -> Result<()> {
+    let days: Vec<MoodDay> = indexed_journal_files("journal")?
+        .into_iter()
+        .filter_map(|(file, info)| {
+            let mood = info.fields.get("mood")?.parse().ok()?;
+            let (high_f, weather) = info
+                .fields
+                .get("weather")
+                .map(|w| parse_weather(w))
+                .unwrap_or((None, None));
+            Some(MoodDay {
+                date: file.date()?,
+                mood,
+                energy: info.fields.get("energy").and_then(|e| e.parse().ok()),
+                high_f,
+                weather,
+                sleep_hours: info.fields.get("sleep").and_then(|s| parse_sleep(s)),
+            })
+        })
+        .collect();
+    if days.is_empty() {
+        println!("No moods recorded yet (add today's with `ja mood 4`)");
+        return Ok(());
+    }
+
+    println!("\nMood Analysis");
+    println!("===================================");
+    let mood = average(days.iter().map(|d| d.mood)).unwrap_or(0.0);
+    print!("Average mood {:.1} over {} days", mood, days.len());
+    match average(days.iter().filter_map(|d| d.energy)) {
+        Some(energy) => println!(", energy {:.1}", energy),
+        None => println!(),
+    }
+    let mut months: BTreeMap<(i32, u32), Vec<f64>> = BTreeMap::new();
+    for day in &days {
+        months
+            .entry((day.date.year(), day.date.month()))
+            .or_default()
+            .push(day.mood);
+    }
+    if months.len() > 1 {
+        let averages: Vec<f64> = months
+            .values()
+            .map(|m| m.iter().sum::<f64>() / m.len() as f64)
+            .collect();
+        println!("Monthly: {}", chart::sparkline(&averages));
+    }
+
+    print_groups("By Weekday", &days, |d| {
+        Some((
+            d.date.weekday().num_days_from_monday(),
+            d.date.format("%A").to_string(),
+        ))
+    });
+    print_groups("By Weather", &days, |d| {
+        d.weather.map(|w| (w, w.to_string()))
+    });
+    print_groups("By High Temperature", &days, |d| {
+        let band = (d.high_f? / 20.0).floor() as i32;
+        Some((band, format!("{}-{} F", band * 20, band * 20 + 19)))
+    });
+    print_groups("By Sleep", &days, |d| {
+        let hours = d.sleep_hours?;
+        Some(match hours {
+            h if h < 6.0 => (0, "under 6h".to_string()),
+            h if h < 7.0 => (1, "6-7h".to_string()),
+            h if h < 8.0 => (2, "7-8h".to_string()),
+            _ => (3, "8h or more".to_string()),
+        })
+    });
+
+    // Class days only apply when there are schedules
+    let mut class_days = HashSet::new();
+    if Path::new(&get_schedules_dir()?).exists() {
+        for schedule in load_schedules()? {
+            class_days.extend(schedule.get_class_dates()?);
+        }
+    }
+    if !class_days.is_empty() {
+        print_groups("Class Days", &days, |d| {
+            Some(if class_days.contains(&d.date) {
+                (0, "Class days".to_string())
+            } else {
+                (1, "Other days".to_string())
+            })
+        });
+    }
+
+    println!("\nCorrelations");
+    println!("===================================");
+    let pairs = |f: fn(&MoodDay) -> Option<f64>| -> Vec<(f64, f64)> {
+        days.iter()
+            .filter_map(|d| f(d).map(|x| (x, d.mood)))
+            .collect()
+    };
+    print_correlation("Energy", &pairs(|d| d.energy));
+    print_correlation("Hours of sleep", &pairs(|d| d.sleep_hours));
+    print_correlation("High temperature", &pairs(|d| d.high_f));
+    println!();
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_weather_and_sleep() {
+        assert_eq!(parse_weather("40-55 F ☀️"), (Some(55.0), Some("Sunny")));
+        assert_eq!(parse_weather("-5-12 F ❄️"), (Some(12.0), Some("Snow")));
+        assert_eq!(parse_weather("-12--5 F ❄️"), (Some(-5.0), Some("Snow")));
+        assert_eq!(parse_weather("N/A-N/A F 🌈"), (None, Some("Other")));
+        assert_eq!(parse_sleep("7h 30m"), Some(7.5));
+        assert_eq!(parse_sleep("8h 00m"), Some(8.0));
+        assert_eq!(parse_sleep("—"), None);
+    }
+}
@@ -0,0 +1,227 @@
+use crate::journal::{create_journal_entry, get_todays_journal_path, open_journal_entry};
+use crate::schedule::{load_schedules, parse_time, ClassSchedule, Session};
+use anyhow::Result;
+use chrono::{Duration, Local, NaiveDateTime};
+
+// How far ahead to look for the next class, e.g. across a winter break
+const SEARCH_DAYS: i64 = 60;
+
+struct UpcomingSession {
+    class_name: String,
+    session: Session,
+    start: NaiveDateTime,
+    end: NaiveDateTime,
+}
+
+/// Finds the session in progress at `now`, or else the next one to start.
+fn find_next(schedules: &[ClassSchedule], now: NaiveDateTime) -> Result<Option<UpcomingSession>> {
+    let resolved = schedules
+        .iter()
+        .map(|s| s.resolve())
+        .collect::<Result<Vec<_>>>()?;
+    let today = now.date();
+    for date in today.iter_days().take(SEARCH_DAYS as usize) {
+        let mut upcoming = Vec::new();
+        for (schedule, resolved) in schedules.iter().zip(&resolved) {
+            for session in resolved.sessions_on(date) {
+                let start = date.and_time(parse_time(&session.start_time)?);
+                let end = date.and_time(parse_time(&session.end_time)?);
+                if end > now {
+                    upcoming.push(UpcomingSession {
+                        class_name: schedule.class_name.clone(),
+                        session,
+                        start,
+                        end,
+                    });
+                }
+            }
+        }
+        if let Some(next) = upcoming.into_iter().min_by_key(|s| (s.start, s.end)) {
+            return Ok(Some(next));
+        }
+    }
+    Ok(None)
+}
+
+/// Finds the class whose session covers `now`, counting `grace_minutes` before it starts and
+/// after it ends. When two qualify (e.g. one ending and the next starting), a class that has
+/// already started wins over one that hasn't, then the closest start time.
+fn class_at(
+    schedules: &[ClassSchedule],
+    now: NaiveDateTime,
+    grace_minutes: i64,
+) -> Result<Option<String>> {
+    let grace = Duration::minutes(grace_minutes);
+    let mut candidates = Vec::new();
+    for schedule in schedules {
+        for session in schedule.sessions_on(now.date())? {
+            let start = now.date().and_time(parse_time(&session.start_time)?);
+            let end = now.date().and_time(parse_time(&session.end_time)?);
+            if start - grace <= now && now <= end + grace {
+                candidates.push((start > now, (start - now).abs(), &schedule.class_name));
+            }
+        }
+    }
+    Ok(candidates
+        .into_iter()
+        .min()
+        .map(|(_, _, class_name)| class_name.clone()))
+}
+
+/// Returns the class to start notes for right now, or "journal" when no class is on.
+pub fn detect_class(grace_minutes: i64) -> Result<String> {
+    let schedules = load_schedules()?;
+    let now = Local::now().naive_local();
+    Ok(class_at(&schedules, now, grace_minutes)?.unwrap_or_else(|| "journal".to_string()))
+}
+
+/// Formats a duration as "45m", "2h 05m" or "3d 4h".
+fn format_duration(duration: Duration) -> String {
+    let minutes = duration.num_minutes().max(0);
+    if minutes < 60 {
+        format!("{}m", minutes)
+    } else if minutes < 24 * 60 {
+        format!("{}h {:02}m", minutes / 60, minutes % 60)
+    } else {
+        format!("{}d {}h", minutes / (24 * 60), minutes % (24 * 60) / 60)
+    }
+}
+
+pub fn next_class(open: bool) -> Result<()> {
+    let schedules = load_schedules()?;
+    let now = Local::now().naive_local();
+
+    let next = match find_next(&schedules, now)? {
+        Some(next) => next,
+        None => {
+            println!("No classes in the next {} days.", SEARCH_DAYS);
+            return Ok(());
+        }
+    };
+
+    println!("\nNext Class");
+    println!("===================================");
+    match &next.session.section {
+        Some(section) => println!("Course: {} ({})", next.class_name, section),
+        None => println!("Course: {}", next.class_name),
+    }
+    let day = if next.start.date() == now.date() {
+        "Today".to_string()
+    } else {
+        next.start.format("%A, %B %d").to_string()
+    };
+    println!(
+        "Time: {} {} - {}",
+        day, next.session.start_time, next.session.end_time
+    );
+    if next.start <= now {
+        println!(
+            "Status: in progress, ends in {}",
+            format_duration(next.end - now)
+        );
+    } else {
+        println!("Status: starts in {}", format_duration(next.start - now));
+    }
+    if let Some(location) = &next.session.location {
+        println!("Location: {}", location);
+    }
+    if let Some(instructor) = &next.session.instructor {
+        println!("Instructor: {}", instructor);
+    }
+
+    if open {
+        if next.start.date() != now.date() {
+            println!("\nThe next class isn't today, so there are no notes to open yet.");
+            return Ok(());
+        }
+        // Start the notes with the class header the first time, otherwise just open them
+        let journal_path = get_todays_journal_path(&next.class_name)?;
+        let started = std::fs::read_to_string(&journal_path)
+            .map(|contents| !contents.trim().is_empty())
+            .unwrap_or(false);
+        if started {
+            open_journal_entry(&next.class_name)?;
+        } else {
+            create_journal_entry(&journal_path, &next.class_name, true)?;
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    #[test]
+    fn test_find_next() {
+        let json = r#"{
+            "class_name": "CS101",
+            "start_date": "2024-01-15",
+            "end_date": "2024-05-15",
+            "schedule": [
+                { "weekday": "monday", "start_time": "10:00", "end_time": "11:30" },
+                { "weekday": "wednesday", "start_time": "13:00", "end_time": "14:00", "location": "Room 101" }
+            ]
+        }"#;
+        let schedules = vec![serde_json::from_str::<ClassSchedule>(json).unwrap()];
+        let monday = date(2024, 1, 15);
+
+        // During Monday's class it is the current session
+        let now = monday.and_hms_opt(11, 0, 0).unwrap();
+        let next = find_next(&schedules, now).unwrap().unwrap();
+        assert_eq!(next.start, monday.and_hms_opt(10, 0, 0).unwrap());
+        assert_eq!(format_duration(next.end - now), "30m");
+
+        // After it ends, Wednesday's class is next
+        let now = monday.and_hms_opt(12, 0, 0).unwrap();
+        let next = find_next(&schedules, now).unwrap().unwrap();
+        assert_eq!(next.session.location.as_deref(), Some("Room 101"));
+        assert_eq!(format_duration(next.start - now), "2d 1h");
+
+        // Nothing after the term ends
+        let now = date(2024, 6, 1).and_hms_opt(9, 0, 0).unwrap();
+        assert!(find_next(&schedules, now).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_class_at() {
+        let schedule = |class_name: &str, start_time: &str, end_time: &str| {
+            let json = format!(
+                r#"{{
+                    "class_name": "{}",
+                    "start_date": "2024-01-15",
+                    "end_date": "2024-05-15",
+                    "schedule": [{{ "weekday": "monday", "start_time": "{}", "end_time": "{}" }}]
+                }}"#,
+                class_name, start_time, end_time
+            );
+            serde_json::from_str::<ClassSchedule>(&json).unwrap()
+        };
+        let schedules = vec![
+            schedule("CS101", "10:00", "11:30"),
+            schedule("MATH241", "11:35", "12:30"),
+        ];
+        let monday = date(2024, 1, 15);
+        let at = |hour, minute| {
+            class_at(&schedules, monday.and_hms_opt(hour, minute, 0).unwrap(), 10).unwrap()
+        };
+
+        // Ten minutes early still counts as class time, but not half an hour after
+        assert_eq!(at(9, 50).as_deref(), Some("CS101"));
+        assert_eq!(at(13, 0), None);
+
+        // Between back-to-back classes the one that has started wins
+        assert_eq!(at(11, 32).as_deref(), Some("CS101"));
+        assert_eq!(at(11, 36).as_deref(), Some("MATH241"));
+
+        // No class on other days
+        let tuesday = date(2024, 1, 16).and_hms_opt(10, 0, 0).unwrap();
+        assert_eq!(class_at(&schedules, tuesday, 10).unwrap(), None);
+    }
+}
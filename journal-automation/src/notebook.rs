@@ -0,0 +1,218 @@
+use crate::utils::get_git_root;
+use anyhow::Result;
+use chrono::NaiveDate;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// A daily entry in a notebook: the `journal` itself or a class such as `CS201`.
+#[derive(Debug)]
+pub struct JournalFile {
+    pub notebook: String,
+    pub path: PathBuf,
+    pub year: i32,
+    pub month: u32,
+    pub day: u32,
+    pub weekday: String,
+}
+
+impl JournalFile {
+    pub fn date(&self) -> Option<NaiveDate> {
+        NaiveDate::from_ymd_opt(self.year, self.month, self.day)
+    }
+}
+
+// Daily entries live at `YYYY/MM-mon/DD_Weekday.md` inside a notebook folder. Paths are built and
+// parsed only here so the two stay in step.
+
+/// The `YYYY/MM-mon` folder for a date, relative to the notebook.
+pub fn month_folder(date: NaiveDate) -> String {
+    format!(
+        "{}/{}-{}",
+        date.format("%Y"),
+        date.format("%m"),
+        date.format("%b").to_string().to_lowercase()
+    )
+}
+
+/// The `YYYY/MM-month` folder with the full month name, which `start-journal` used before it shared
+/// the layout with `create-year`. Entries in these folders are still found until they are renamed.
+pub fn legacy_month_folder(date: NaiveDate) -> String {
+    format!(
+        "{}/{}-{}",
+        date.format("%Y"),
+        date.format("%m"),
+        date.format("%B").to_string().to_lowercase()
+    )
+}
+
+/// The `DD_Weekday.md` file name for a date.
+pub fn entry_file_name(date: NaiveDate) -> String {
+    format!("{}_{}.md", date.format("%d"), date.format("%A"))
+}
+
+/// Reads an entry path relative to the notebook root, so folders above the notebook (which may
+/// contain years or hyphens of their own) are never looked at. The day only has to be 01-31 so
+/// that impossible dates can still be reported by validation.
+fn parse_entry_path(notebook_root: &Path, path: &Path, notebook: &str) -> Option<JournalFile> {
+    let relative = path.strip_prefix(notebook_root).ok()?;
+    let parts: Vec<&str> = relative.iter().map(|p| p.to_str()).collect::<Option<_>>()?;
+    let [year, month, file_name] = parts.as_slice() else {
+        return None;
+    };
+
+    let two_digits = |s: &str| s.len() == 2 && s.chars().all(|c| c.is_ascii_digit());
+    if year.len() != 4 || !year.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    let (month, _) = month.split_once('-')?;
+    let (day, weekday) = file_name.strip_suffix(".md")?.split_once('_')?;
+    if !two_digits(month) || !two_digits(day) || weekday.is_empty() {
+        return None;
+    }
+    let day: u32 = day.parse().ok()?;
+    if !(1..=31).contains(&day) {
+        return None;
+    }
+
+    Some(JournalFile {
+        notebook: notebook.to_string(),
+        path: path.to_path_buf(),
+        year: year.parse().ok()?,
+        month: month.parse().ok()?,
+        day,
+        weekday: weekday.to_string(),
+    })
+}
+
+/// Walks a notebook folder and returns its daily entries sorted by path.
+pub fn walk_notebook(notebook: &str) -> Result<Vec<JournalFile>> {
+    let root = Path::new(&get_git_root()?).join(notebook);
+    let mut files: Vec<JournalFile> = walkdir::WalkDir::new(&root)
+        .min_depth(3)
+        .max_depth(3)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+        .filter_map(|e| parse_entry_path(&root, e.path(), notebook))
+        .collect();
+    files.sort_by(|a, b| a.path.cmp(&b.path));
+    Ok(files)
+}
+
+/// Lists the folders in the repository that hold year folders, with `journal` first.
+pub fn list_notebooks() -> Result<Vec<String>> {
+    let git_root = get_git_root()?;
+    let mut notebooks = Vec::new();
+    for entry in fs::read_dir(&git_root)?.filter_map(|e| e.ok()) {
+        let name = entry.file_name().to_string_lossy().to_string();
+        if name.starts_with('.') || name == "journal-automation" || !entry.path().is_dir() {
+            continue;
+        }
+        let has_years = fs::read_dir(entry.path())?.filter_map(|e| e.ok()).any(|e| {
+            let year = e.file_name().to_string_lossy().to_string();
+            e.path().is_dir() && year.len() == 4 && year.chars().all(|c| c.is_ascii_digit())
+        });
+        if has_years {
+            notebooks.push(name);
+        }
+    }
+    notebooks.sort_by_key(|n| (n != "journal", n.clone()));
+    Ok(notebooks)
+}
+
+/// Resolves the `--class`/`--all` options of the scanning commands to notebook names.
+pub fn select_notebooks(class: Option<&str>, all: bool) -> Result<Vec<String>> {
+    if all {
+        return list_notebooks();
+    }
+    let Some(class) = class else {
+        return Ok(vec!["journal".to_string()]);
+    };
+
+    let notebooks = list_notebooks()?;
+    if !notebooks.iter().any(|n| n == class) {
+        anyhow::bail!(
+            "No notebook named {} (known notebooks: {})",
+            class,
+            notebooks.join(", ")
+        );
+    }
+    Ok(vec![class.to_string()])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    fn parse(root: &str, relative: &str) -> Option<JournalFile> {
+        let root = Path::new(root);
+        parse_entry_path(root, &root.join(relative), "CS201")
+    }
+
+    #[test]
+    fn test_parse_entry_path() {
+        let file = parse("/notes/CS201", "2024/01-jan/15_Monday.md").unwrap();
+        assert_eq!(file.notebook, "CS201");
+        assert_eq!((file.year, file.month, file.day), (2024, 1, 15));
+        assert_eq!(file.weekday, "Monday");
+
+        // Impossible dates are kept for validation, other files are not entries
+        let root = "/notes/CS201";
+        assert!(parse(root, "2024/02-feb/30_Friday.md")
+            .unwrap()
+            .date()
+            .is_none());
+        assert!(parse(root, "2024/01-jan/January 2024 Happenings.md").is_none());
+        assert!(parse(root, "2024/01-jan/32_Monday.md").is_none());
+        assert!(parse(root, "2024/01-jan/15_Monday.txt").is_none());
+        assert!(parse(root, "2024/jan-01/15_Monday.md").is_none());
+        assert!(parse(root, "notes/01-jan/15_Monday.md").is_none());
+        assert!(parse("/other", "/notes/CS201/2024/01-jan/15_Monday.md").is_none());
+    }
+
+    fn any_date() -> impl Strategy<Value = NaiveDate> {
+        (0..365 * 130i64).prop_map(|days| {
+            NaiveDate::from_ymd_opt(1970, 1, 1).unwrap() + chrono::Duration::days(days)
+        })
+    }
+
+    // Folder names above the notebook that look like years or months
+    fn any_root() -> impl Strategy<Value = String> {
+        prop::collection::vec(
+            "[a-z]{1,6}(-[a-z0-9]{1,6})?|[0-9]{4}(-[a-z]{1,8})?|[0-9]{2}-[a-z]{3}",
+            0..5,
+        )
+        .prop_map(|parts| format!("/{}", parts.join("/")))
+    }
+
+    proptest! {
+        #[test]
+        fn prop_layout_round_trips(date in any_date(), root in any_root()) {
+            let notebook_root = Path::new(&root).join("CS201");
+            let path = notebook_root
+                .join(month_folder(date))
+                .join(entry_file_name(date));
+
+            let file = parse_entry_path(&notebook_root, &path, "CS201").unwrap();
+            prop_assert_eq!(file.date(), Some(date));
+            prop_assert_eq!(file.weekday, date.format("%A").to_string());
+            prop_assert_eq!(file.path, path);
+        }
+
+        #[test]
+        fn prop_other_depths_are_not_entries(date in any_date(), root in any_root()) {
+            let notebook_root = Path::new(&root).join("CS201");
+            let too_shallow = notebook_root.join(entry_file_name(date));
+            let too_deep = notebook_root
+                .join(month_folder(date))
+                .join("attachments")
+                .join(entry_file_name(date));
+
+            prop_assert!(parse_entry_path(&notebook_root, &too_shallow, "CS201").is_none());
+            prop_assert!(parse_entry_path(&notebook_root, &too_deep, "CS201").is_none());
+        }
+    }
+}
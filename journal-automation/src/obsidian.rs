@@ -0,0 +1,83 @@
+use crate::journal::get_month_folder;
+use crate::utils::get_git_root;
+use anyhow::{Context, Result};
+use chrono::Local;
+use serde_json::Value;
+use std::fs;
+
+/// Points the daily-notes `folder` at `new_folder`. A `template` that lived under the old folder
+/// is moved along with it. Returns the previous folder, or `None` if nothing changed.
+fn rollover_config(config: &mut Value, new_folder: &str) -> Result<Option<String>> {
+    let settings = config
+        .as_object_mut()
+        .ok_or_else(|| anyhow::anyhow!("daily-notes.json is not a JSON object"))?;
+
+    let old_folder = settings
+        .get("folder")
+        .and_then(|f| f.as_str())
+        .unwrap_or_default()
+        .to_string();
+    if old_folder == new_folder {
+        return Ok(None);
+    }
+
+    if !old_folder.is_empty() {
+        if let Some(template) = settings.get("template").and_then(|t| t.as_str()) {
+            if let Some(rest) = template.strip_prefix(&old_folder) {
+                let new_template = format!("{}{}", new_folder, rest);
+                settings.insert("template".to_string(), Value::String(new_template));
+            }
+        }
+    }
+
+    settings.insert("folder".to_string(), Value::String(new_folder.to_string()));
+    Ok(Some(old_folder))
+}
+
+pub fn rollover_daily_notes() -> Result<()> {
+    let config_path = format!("{}/.obsidian/daily-notes.json", get_git_root()?);
+    let contents = fs::read_to_string(&config_path)
+        .with_context(|| format!("Failed to read daily notes config: {}", config_path))?;
+    let mut config: Value = serde_json::from_str(&contents)
+        .with_context(|| format!("Failed to parse daily notes config: {}", config_path))?;
+
+    let new_folder = get_month_folder(Local::now().date_naive(), "journal")?;
+    match rollover_config(&mut config, &new_folder)? {
+        Some(old_folder) => {
+            fs::write(&config_path, serde_json::to_string_pretty(&config)?)
+                .with_context(|| format!("Failed to write daily notes config: {}", config_path))?;
+            println!("Moved daily notes folder: {} -> {}", old_folder, new_folder);
+        }
+        None => println!("Daily notes folder already set to {}", new_folder),
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rollover_config() {
+        let mut config: Value = serde_json::from_str(
+            r#"{
+                "folder": "journal/2022/07-jul",
+                "template": "journal/2022/07-jul/template",
+                "format": "DD_dddd"
+            }"#,
+        )
+        .unwrap();
+
+        let old = rollover_config(&mut config, "journal/2022/08-aug").unwrap();
+        assert_eq!(old.as_deref(), Some("journal/2022/07-jul"));
+        assert_eq!(config["folder"], "journal/2022/08-aug");
+        assert_eq!(config["template"], "journal/2022/08-aug/template");
+        assert_eq!(config["format"], "DD_dddd");
+
+        // Running again for the same month is a no-op
+        assert!(rollover_config(&mut config, "journal/2022/08-aug")
+            .unwrap()
+            .is_none());
+    }
+}
@@ -0,0 +1,252 @@
+use crate::chart;
+use crate::journal::{add_transcribed_header, get_journal_path_for_date};
+use crate::utils::{get_git_root, open_in_editor};
+use anyhow::{Context, Result};
+use chrono::{Local, NaiveDate};
+use serde::{Deserialize, Serialize};
+use std::{
+    fs::{self, File},
+    io::ErrorKind,
+    path::Path,
+};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PaperVolume {
+    pub name: String,
+    pub start_date: String, // YYYY-MM-DD
+    pub end_date: String,   // YYYY-MM-DD
+    pub added_on: String,   // YYYY-MM-DD
+}
+
+impl PaperVolume {
+    pub fn get_dates(&self) -> Result<Vec<NaiveDate>> {
+        let start_date = NaiveDate::parse_from_str(&self.start_date, "%Y-%m-%d")
+            .with_context(|| format!("Invalid start date format: {}", self.start_date))?;
+        let end_date = NaiveDate::parse_from_str(&self.end_date, "%Y-%m-%d")
+            .with_context(|| format!("Invalid end date format: {}", self.end_date))?;
+
+        Ok(start_date
+            .iter_days()
+            .take_while(|d| *d <= end_date)
+            .collect())
+    }
+}
+
+// Folder inside journal-automation that holds the paper volumes
+pub const DATA_DIR: &str = "paper";
+
+fn volumes_path() -> Result<String> {
+    Ok(format!(
+        "{}/journal-automation/{}/volumes.json",
+        get_git_root()?,
+        DATA_DIR
+    ))
+}
+
+fn load_volumes() -> Result<Vec<PaperVolume>> {
+    let path = volumes_path()?;
+    if !Path::new(&path).exists() {
+        return Ok(Vec::new());
+    }
+
+    let contents = fs::read_to_string(&path)
+        .with_context(|| format!("Failed to read paper volumes: {}", path))?;
+    serde_json::from_str(&contents)
+        .with_context(|| format!("Failed to parse paper volumes: {}", path))
+}
+
+fn save_volumes(volumes: &[PaperVolume]) -> Result<()> {
+    let path = volumes_path()?;
+    if let Some(parent) = Path::new(&path).parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    fs::write(&path, serde_json::to_string_pretty(volumes)? + "\n")
+        .with_context(|| format!("Failed to write paper volumes: {}", path))
+}
+
+/// Whether an entry has anything besides the date heading and `> Transcribed on:` line that
+/// `next` writes before opening the editor.
+fn has_transcription(contents: &str) -> bool {
+    contents.lines().map(str::trim).any(|line| {
+        !line.is_empty() && !line.starts_with("# ") && !line.starts_with("> Transcribed on:")
+    })
+}
+
+/// A day is transcribed once its entry exists and has content.
+fn is_transcribed(date: NaiveDate) -> Result<bool> {
+    let path = get_journal_path_for_date(date, "journal")?;
+    match fs::read_to_string(&path) {
+        Ok(contents) => Ok(has_transcription(&contents)),
+        Err(e) if e.kind() == ErrorKind::NotFound => Ok(false),
+        Err(e) => Err(e).with_context(|| format!("Failed to read {}", path)),
+    }
+}
+
+pub fn add_volume(name: &str, start_date: &str, end_date: &str) -> Result<()> {
+    let mut volumes = load_volumes()?;
+    if volumes.iter().any(|v| v.name == name) {
+        anyhow::bail!("Paper volume already exists: {}", name);
+    }
+
+    let volume = PaperVolume {
+        name: name.to_string(),
+        start_date: start_date.to_string(),
+        end_date: end_date.to_string(),
+        added_on: Local::now().format("%Y-%m-%d").to_string(),
+    };
+    let dates = volume.get_dates()?;
+    if dates.is_empty() {
+        anyhow::bail!("End date {} is before start date {}", end_date, start_date);
+    }
+
+    // Create empty placeholder files for any days that don't exist yet
+    let mut created = 0;
+    for &date in &dates {
+        let path = get_journal_path_for_date(date, "journal")?;
+        if !Path::new(&path).exists() {
+            if let Some(parent) = Path::new(&path).parent() {
+                fs::create_dir_all(parent)?;
+            }
+            File::create(&path)?;
+            created += 1;
+        }
+    }
+
+    volumes.push(volume);
+    save_volumes(&volumes)?;
+
+    println!(
+        "Added paper volume {} ({} days, {} new files)",
+        name,
+        dates.len(),
+        created
+    );
+    Ok(())
+}
+
+pub fn paper_status() -> Result<()> {
+    let volumes = load_volumes()?;
+
+    println!("\nPaper Journal Conversion");
+    println!("===================================");
+    println!();
+
+    if volumes.is_empty() {
+        println!("No paper volumes recorded. Add one with `ja paper add`.");
+        return Ok(());
+    }
+
+    let max_name_width = volumes.iter().map(|v| v.name.len()).max().unwrap_or(1);
+
+    for volume in &volumes {
+        let dates = volume.get_dates()?;
+        let mut transcribed = 0;
+        for &date in &dates {
+            if is_transcribed(date)? {
+                transcribed += 1;
+            }
+        }
+        let remaining = dates.len() - transcribed;
+        let completion_rate = if dates.is_empty() {
+            0.0
+        } else {
+            (transcribed as f64 / dates.len() as f64) * 100.0
+        };
+
+        let bar = chart::bar(completion_rate, 20);
+        let completion_marker = if remaining == 0 { " ✓" } else { "" };
+
+        println!(
+            "{:<name_width$} | {} to {} | {:>6.1}% {} {} remain{}",
+            volume.name,
+            volume.start_date,
+            volume.end_date,
+            completion_rate,
+            bar,
+            remaining,
+            completion_marker,
+            name_width = max_name_width
+        );
+    }
+
+    println!();
+    Ok(())
+}
+
+pub fn next_pages(pages: usize, volume_name: Option<&str>) -> Result<()> {
+    let volumes = load_volumes()?;
+    let mut remaining = Vec::new();
+
+    for volume in volumes
+        .iter()
+        .filter(|v| volume_name.is_none_or(|name| v.name == name))
+    {
+        for date in volume.get_dates()? {
+            if !is_transcribed(date)? {
+                remaining.push(date);
+            }
+        }
+    }
+
+    if remaining.is_empty() {
+        match volume_name {
+            Some(name) => anyhow::bail!("No untranscribed days left in paper volume {}", name),
+            None => anyhow::bail!("No untranscribed days left in any paper volume"),
+        }
+    }
+
+    remaining.sort();
+    remaining.dedup();
+
+    for date in remaining.into_iter().take(pages) {
+        let path = get_journal_path_for_date(date, "journal")?;
+        if let Some(parent) = Path::new(&path).parent() {
+            fs::create_dir_all(parent)?;
+        }
+        // A stub left from an earlier run already has its header
+        if fs::metadata(&path).map(|m| m.len() == 0).unwrap_or(true) {
+            File::create(&path)?;
+            add_transcribed_header(Path::new(&path), date)?;
+        }
+        println!(
+            "Opening paper journal entry: {}",
+            date.format("%A, %B %d, %Y")
+        );
+        open_in_editor(&path)?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_volume_dates() {
+        let volume = PaperVolume {
+            name: "Blue notebook".to_string(),
+            start_date: "2019-02-27".to_string(),
+            end_date: "2019-03-02".to_string(),
+            added_on: "2024-01-01".to_string(),
+        };
+
+        let dates = volume.get_dates().unwrap();
+        assert_eq!(dates.len(), 4);
+        assert_eq!(dates[0], NaiveDate::from_ymd_opt(2019, 2, 27).unwrap());
+        assert_eq!(dates[3], NaiveDate::from_ymd_opt(2019, 3, 2).unwrap());
+    }
+
+    #[test]
+    fn test_has_transcription() {
+        let stub = "# Wednesday, February 27, 2019\n\n> Transcribed on: 03/15/2024\n\n";
+        assert!(!has_transcription(""));
+        assert!(!has_transcription(stub));
+        assert!(has_transcription(&format!(
+            "{}Snow day, no school.\n",
+            stub
+        )));
+        assert!(has_transcription(&format!("{}## Notes\n", stub)));
+    }
+}
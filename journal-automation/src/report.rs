@@ -0,0 +1,408 @@
+use crate::index::EntryInfo;
+use crate::journal::{completion_by_year, indexed_journal_files};
+use crate::notebook::JournalFile;
+use crate::stats::streaks;
+use anyhow::{Context, Result};
+use chrono::{Datelike, Duration, Local, NaiveDate};
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt::Write,
+    fs,
+    path::Path,
+};
+
+// Empty, then four shades from the fewest words to the most
+const SHADES: [&str; 5] = ["#ebedf0", "#c6e48b", "#7bc96f", "#239a3b", "#196127"];
+const ACCENT: &str = "#239a3b";
+const GRID: &str = "#e1e4e8";
+
+const STYLE: &str = "
+body { font-family: -apple-system, 'Segoe UI', Helvetica, Arial, sans-serif; color: #24292e;
+       max-width: 900px; margin: 2em auto; padding: 0 1em; }
+h1 { border-bottom: 1px solid #e1e4e8; padding-bottom: 0.3em; }
+h2 { margin-top: 2em; }
+.summary { display: flex; flex-wrap: wrap; gap: 1em; }
+.summary div { border: 1px solid #e1e4e8; border-radius: 6px; padding: 0.6em 1em; }
+.summary b { display: block; font-size: 1.6em; }
+svg text { font-size: 11px; fill: #586069; }
+table { border-collapse: collapse; }
+td, th { padding: 0.2em 1em 0.2em 0; text-align: left; }
+.tags span { display: inline-block; margin: 0 0.5em 0.3em 0; color: #0366d6; }
+";
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Horizontal bars with a label on the left and the value on the right.
+fn svg_bars(items: &[(String, f64)], max: f64, unit: &str) -> String {
+    let (label_width, bar_width, row) = (70.0, 500.0, 24.0);
+    let mut svg = format!(
+        r#"<svg width="{}" height="{}" xmlns="http://www.w3.org/2000/svg">"#,
+        label_width + bar_width + 80.0,
+        items.len() as f64 * row
+    );
+    for (i, (label, value)) in items.iter().enumerate() {
+        let y = i as f64 * row;
+        let width = if max > 0.0 {
+            value / max * bar_width
+        } else {
+            0.0
+        };
+        let _ = write!(
+            svg,
+            r#"<text x="0" y="{:.0}">{}</text><rect x="{}" y="{:.0}" width="{:.1}" height="{:.0}" fill="{}"/><text x="{:.1}" y="{:.0}">{:.1}{}</text>"#,
+            y + 16.0,
+            escape(label),
+            label_width,
+            y + 4.0,
+            width,
+            row - 8.0,
+            ACCENT,
+            label_width + width + 6.0,
+            y + 16.0,
+            value,
+            unit
+        );
+    }
+    svg + "</svg>"
+}
+
+/// A line through the values with the range on the y-axis and the first and last labels below.
+fn svg_line(items: &[(String, f64)]) -> String {
+    let (width, height, left, bottom) = (720.0, 200.0, 50.0, 20.0);
+    let min = items.iter().map(|(_, v)| *v).fold(f64::INFINITY, f64::min);
+    let max = items
+        .iter()
+        .map(|(_, v)| *v)
+        .fold(f64::NEG_INFINITY, f64::max);
+    let span = if max > min { max - min } else { 1.0 };
+    let step = (width - left) / (items.len().max(2) - 1) as f64;
+
+    let points: Vec<String> = items
+        .iter()
+        .enumerate()
+        .map(|(i, (_, v))| {
+            let x = left + i as f64 * step;
+            let y = 5.0 + (1.0 - (v - min) / span) * (height - bottom - 10.0);
+            format!("{:.1},{:.1}", x, y)
+        })
+        .collect();
+
+    let mut svg = format!(
+        r#"<svg width="{}" height="{}" xmlns="http://www.w3.org/2000/svg">"#,
+        width, height
+    );
+    let _ = write!(
+        svg,
+        r#"<line x1="{left}" y1="0" x2="{left}" y2="{axis}" stroke="{grid}"/><line x1="{left}" y1="{axis}" x2="{width}" y2="{axis}" stroke="{grid}"/>"#,
+        grid = GRID,
+        left = left,
+        axis = height - bottom,
+        width = width
+    );
+    let _ = write!(
+        svg,
+        r#"<text x="0" y="12">{:.0}</text><text x="0" y="{}">{:.0}</text>"#,
+        max,
+        height - bottom,
+        min
+    );
+    if let (Some((first, _)), Some((last, _))) = (items.first(), items.last()) {
+        let _ = write!(
+            svg,
+            r#"<text x="{}" y="{}">{}</text><text x="{}" y="{}" text-anchor="end">{}</text>"#,
+            left,
+            height - 4.0,
+            escape(first),
+            width,
+            height - 4.0,
+            escape(last)
+        );
+    }
+    let _ = write!(
+        svg,
+        r#"<polyline points="{}" fill="none" stroke="{}" stroke-width="2"/>"#,
+        points.join(" "),
+        ACCENT
+    );
+    svg + "</svg>"
+}
+
+/// A year of days as a calendar with a column per week, shaded by words written.
+fn svg_heatmap(year: i32, words: &BTreeMap<NaiveDate, u64>) -> String {
+    let cell = 13.0;
+    let (Some(first), Some(last)) = (
+        NaiveDate::from_ymd_opt(year, 1, 1),
+        NaiveDate::from_ymd_opt(year, 12, 31),
+    ) else {
+        return String::new();
+    };
+    let start = first - Duration::days(first.weekday().num_days_from_monday() as i64);
+    let max = words
+        .range(first..=last)
+        .map(|(_, w)| *w)
+        .max()
+        .unwrap_or(0);
+
+    let mut svg = format!(
+        r#"<svg width="{:.0}" height="{:.0}" xmlns="http://www.w3.org/2000/svg">"#,
+        30.0 + 54.0 * cell,
+        20.0 + 7.0 * cell
+    );
+    for (row, name) in [(0, "Mon"), (2, "Wed"), (4, "Fri")] {
+        let _ = write!(
+            svg,
+            r#"<text x="0" y="{:.0}">{}</text>"#,
+            20.0 + row as f64 * cell + 10.0,
+            name
+        );
+    }
+
+    let mut date = first;
+    while date <= last {
+        let days = (date - start).num_days();
+        let (x, y) = (
+            30.0 + (days / 7) as f64 * cell,
+            20.0 + (days % 7) as f64 * cell,
+        );
+        if date.day() == 1 {
+            let _ = write!(
+                svg,
+                r#"<text x="{:.0}" y="12">{}</text>"#,
+                x,
+                date.format("%b")
+            );
+        }
+        let (shade, title) = match words.get(&date) {
+            Some(&w) if w > 0 && max > 0 => (
+                SHADES[1 + ((w as f64 / max as f64) * 3.0).round() as usize],
+                format!("{}: {} words", date.format("%a %b %d"), w),
+            ),
+            _ => (SHADES[0], format!("{}: no entry", date.format("%a %b %d"))),
+        };
+        let _ = write!(
+            svg,
+            r#"<rect x="{:.0}" y="{:.0}" width="{:.0}" height="{:.0}" rx="2" fill="{}"><title>{}</title></rect>"#,
+            x,
+            y,
+            cell - 2.0,
+            cell - 2.0,
+            shade,
+            title
+        );
+        date += Duration::days(1);
+    }
+    svg + "</svg>"
+}
+
+/// Tags sized by how many entries use them, most common first.
+fn tag_cloud(counts: &HashMap<&str, usize>) -> String {
+    let mut tags: Vec<(&str, usize)> = counts.iter().map(|(t, c)| (*t, *c)).collect();
+    tags.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+    tags.truncate(80);
+    let max = tags.first().map_or(1, |(_, c)| *c) as f64;
+
+    let mut html = String::from(r#"<div class="tags">"#);
+    for (tag, count) in tags {
+        let _ = write!(
+            html,
+            r#"<span style="font-size: {:.2}em" title="{} entries">#{}</span>"#,
+            0.8 + (count as f64 / max) * 1.6,
+            count,
+            escape(tag)
+        );
+    }
+    html + "</div>"
+}
+
+fn build_html(title: &str, entries: &[(JournalFile, EntryInfo)]) -> String {
+    let today = Local::now().date_naive();
+    let written: Vec<(NaiveDate, &EntryInfo)> = entries
+        .iter()
+        .filter(|(_, info)| info.size > 0)
+        .filter_map(|(file, info)| file.date().map(|date| (date, info)))
+        .collect();
+    let mut dates: Vec<NaiveDate> = written.iter().map(|(date, _)| *date).collect();
+    dates.sort();
+    let runs = streaks(&dates);
+    let longest = runs
+        .iter()
+        .max_by_key(|(first, last)| (*last - *first, *first));
+    // Today's entry may not be written yet, so a streak ending yesterday is still going
+    let current = runs
+        .last()
+        .filter(|(_, last)| *last >= today - Duration::days(1));
+    let streak_days = |(first, last): &(NaiveDate, NaiveDate)| (*last - *first).num_days() + 1;
+
+    let mut html = String::new();
+    let _ = write!(
+        html,
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n<style>{}</style>\n</head>\n<body>\n<h1>{}</h1>\n",
+        escape(title),
+        STYLE,
+        escape(title)
+    );
+
+    // Summary
+    let total_words: u64 = written.iter().map(|(_, info)| info.words).sum();
+    let _ = writeln!(
+        html,
+        r#"<div class="summary"><div><b>{}</b>entries written</div><div><b>{}</b>words</div><div><b>{}</b>longest streak</div><div><b>{}</b>current streak</div></div>"#,
+        written.len(),
+        total_words,
+        longest.map_or(0, streak_days),
+        current.map_or(0, streak_days)
+    );
+
+    // Completion
+    let completion: Vec<(String, f64)> = completion_by_year(entries)
+        .iter()
+        .map(|(year, stats)| (year.to_string(), stats.completion_rate()))
+        .collect();
+    let _ = writeln!(
+        html,
+        "<h2>Completion</h2>\n{}",
+        svg_bars(&completion, 100.0, "%")
+    );
+
+    // Length by month
+    let mut months: BTreeMap<String, (u64, u64)> = BTreeMap::new();
+    for (date, info) in &written {
+        let month = months.entry(date.format("%Y-%m").to_string()).or_default();
+        month.0 += info.words;
+        month.1 += 1;
+    }
+    let averages: Vec<(String, f64)> = months
+        .into_iter()
+        .map(|(month, (words, count))| (month, words as f64 / count as f64))
+        .collect();
+    if averages.len() > 1 {
+        let _ = writeln!(
+            html,
+            "<h2>Average Words Per Entry</h2>\n{}",
+            svg_line(&averages)
+        );
+    }
+
+    // Streaks
+    let mut longest_runs: Vec<&(NaiveDate, NaiveDate)> = runs.iter().collect();
+    longest_runs.sort_by_key(|run| (std::cmp::Reverse(streak_days(run)), run.0));
+    let _ = writeln!(
+        html,
+        "<h2>Longest Streaks</h2>\n<table><tr><th>Days</th><th>From</th><th>To</th></tr>"
+    );
+    for run in longest_runs.into_iter().take(5) {
+        let _ = writeln!(
+            html,
+            "<tr><td>{}</td><td>{}</td><td>{}</td></tr>",
+            streak_days(run),
+            run.0.format("%B %d, %Y"),
+            run.1.format("%B %d, %Y")
+        );
+    }
+    html.push_str("</table>\n");
+
+    // Heatmaps, newest year first
+    let words: BTreeMap<NaiveDate, u64> = written
+        .iter()
+        .map(|(date, info)| (*date, info.words))
+        .collect();
+    let mut years: Vec<i32> = completion_by_year(entries).into_keys().collect();
+    years.reverse();
+    html.push_str("<h2>Days Written</h2>\n");
+    for year in years {
+        let _ = writeln!(html, "<h3>{}</h3>\n{}", year, svg_heatmap(year, &words));
+    }
+
+    // Tags
+    let mut tag_counts: HashMap<&str, usize> = HashMap::new();
+    for (_, info) in &written {
+        for tag in &info.tags {
+            *tag_counts.entry(tag.as_str()).or_default() += 1;
+        }
+    }
+    if !tag_counts.is_empty() {
+        let _ = writeln!(html, "<h2>Tags</h2>\n{}", tag_cloud(&tag_counts));
+    }
+
+    let _ = write!(
+        html,
+        "<p><small>Generated {}</small></p>\n</body>\n</html>\n",
+        today.format("%B %d, %Y")
+    );
+    html
+}
+
+/// Writes `index.html` with charts of a notebook's completion, length, streaks, days and tags.
+/// With several notebooks, each gets its own `{notebook}/index.html` in the folder.
+pub fn html_report(out_dir: &str, notebooks: &[String], year: Option<i32>) -> Result<()> {
+    let mut written = 0;
+    for notebook in notebooks {
+        let entries: Vec<(JournalFile, EntryInfo)> = indexed_journal_files(notebook)?
+            .into_iter()
+            .filter(|(file, _)| year.is_none_or(|y| file.year == y))
+            .collect();
+        if entries.is_empty() {
+            println!("No entries found in {}", notebook);
+            continue;
+        }
+
+        let name = if notebook == "journal" {
+            "Journal".to_string()
+        } else {
+            notebook.to_string()
+        };
+        let title = match year {
+            Some(year) => format!("{} {} in Review", name, year),
+            None => format!("{} Review", name),
+        };
+
+        let mut dir = Path::new(out_dir).to_path_buf();
+        if notebooks.len() > 1 {
+            dir.push(notebook);
+        }
+        fs::create_dir_all(&dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+        let path = dir.join("index.html");
+        fs::write(&path, build_html(&title, &entries))
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+
+        println!("Wrote {}", path.display());
+        written += 1;
+    }
+
+    if written == 0 {
+        anyhow::bail!("No entries found in {}", notebooks.join(", "));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_escape_and_tag_cloud() {
+        assert_eq!(
+            escape("<b>\"R&D\"</b>"),
+            "&lt;b&gt;&quot;R&amp;D&quot;&lt;/b&gt;"
+        );
+
+        let counts = HashMap::from([("travel", 4), ("<x>", 1)]);
+        let html = tag_cloud(&counts);
+        assert!(html.find("#travel").unwrap() < html.find("#&lt;x&gt;").unwrap());
+        assert!(html.contains(r#"font-size: 2.40em" title="4 entries""#));
+    }
+
+    #[test]
+    fn test_heatmap_has_every_day() {
+        let words = BTreeMap::from([(NaiveDate::from_ymd_opt(2024, 3, 1).unwrap(), 100)]);
+        let svg = svg_heatmap(2024, &words);
+        assert_eq!(svg.matches("<rect").count(), 366);
+        assert!(svg.contains("Fri Mar 01: 100 words"));
+        assert!(svg.contains(SHADES[4]));
+    }
+}
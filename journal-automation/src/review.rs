@@ -0,0 +1,266 @@
+use crate::block::write_block;
+use crate::index::EntryInfo;
+use crate::journal::{completion_by_year, indexed_journal_files};
+use crate::notebook::{month_folder, JournalFile};
+use crate::stats::streaks;
+use crate::utils::get_git_root;
+use anyhow::{Context, Result};
+use chrono::NaiveDate;
+use std::{collections::HashMap, fs, io::ErrorKind, path::Path};
+
+// Bullets taken from each monthly Happenings file
+const HIGHLIGHTS_PER_MONTH: usize = 3;
+
+/// The first few hand-written lines of a Happenings file, without headings or list markers.
+/// Generated blocks such as the month summary are skipped.
+fn highlights(contents: &str) -> Vec<String> {
+    let mut in_block = false;
+    contents
+        .lines()
+        .map(str::trim)
+        .filter(|line| {
+            if line.starts_with("<!-- ja:") {
+                in_block = line.ends_with(":start -->");
+                return false;
+            }
+            !in_block && !line.is_empty() && !line.starts_with('#')
+        })
+        .map(|line| {
+            line.trim_start_matches(['-', '*', '+'])
+                .trim_start()
+                .trim_start_matches("[ ]")
+                .trim_start_matches("[x]")
+                .trim()
+                .to_string()
+        })
+        .filter(|line| !line.is_empty())
+        .take(HIGHLIGHTS_PER_MONTH)
+        .collect()
+}
+
+/// Composes the review. `happenings` holds each month's name and highlights, and entry links are
+/// relative to the year folder that holds the journey file.
+fn review_markdown(
+    year: i32,
+    year_folder: &Path,
+    entries: &[(JournalFile, EntryInfo)],
+    happenings: &[(String, Vec<String>)],
+) -> String {
+    let mut review = format!("## {} in Review\n\n", year);
+
+    let stats = completion_by_year(entries)
+        .remove(&year)
+        .unwrap_or_default();
+    let written: Vec<(&JournalFile, &EntryInfo)> = entries
+        .iter()
+        .filter(|(_, info)| info.size > 0)
+        .map(|(file, info)| (file, info))
+        .collect();
+    review.push_str(&format!(
+        "- Entries written: {} of {} days ({:.1}%)\n",
+        stats.total_days - stats.empty_days,
+        stats.total_days,
+        stats.completion_rate()
+    ));
+
+    let mut dates: Vec<NaiveDate> = written.iter().filter_map(|(f, _)| f.date()).collect();
+    dates.sort();
+    if let Some((first, last)) = streaks(&dates)
+        .into_iter()
+        .max_by_key(|(first, last)| (*last - *first, std::cmp::Reverse(*first)))
+    {
+        review.push_str(&format!(
+            "- Longest streak: {} days ({} to {})\n",
+            (last - first).num_days() + 1,
+            first.format("%B %d"),
+            last.format("%B %d")
+        ));
+    }
+
+    let total_words: u64 = written.iter().map(|(_, info)| info.words).sum();
+    let average = if written.is_empty() {
+        0.0
+    } else {
+        total_words as f64 / written.len() as f64
+    };
+    review.push_str(&format!(
+        "- Words: {} ({:.0} per entry)\n",
+        total_words, average
+    ));
+
+    let mut tag_counts: HashMap<&str, usize> = HashMap::new();
+    for (_, info) in &written {
+        for tag in &info.tags {
+            *tag_counts.entry(tag.as_str()).or_default() += 1;
+        }
+    }
+    let mut tags: Vec<(&str, usize)> = tag_counts.into_iter().collect();
+    tags.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+    if !tags.is_empty() {
+        let top: Vec<String> = tags
+            .iter()
+            .take(10)
+            .map(|(tag, count)| format!("#{} ({})", tag, count))
+            .collect();
+        review.push_str(&format!("- Most-used tags: {}\n", top.join(", ")));
+    }
+
+    let months: Vec<&(String, Vec<String>)> =
+        happenings.iter().filter(|(_, h)| !h.is_empty()).collect();
+    if !months.is_empty() {
+        review.push_str("\n### Highlights\n");
+        for (month, lines) in months {
+            review.push_str(&format!("\n**{}**\n", month));
+            for line in lines {
+                review.push_str(&format!("- {}\n", line));
+            }
+        }
+    }
+
+    let mut longest = written.clone();
+    longest.sort_by_key(|(file, info)| (std::cmp::Reverse(info.words), file.path.clone()));
+    if !longest.is_empty() {
+        review.push_str("\n### Longest Entries\n\n");
+        for (file, info) in longest.into_iter().take(5) {
+            let Some(date) = file.date() else { continue };
+            let link = file
+                .path
+                .strip_prefix(year_folder)
+                .unwrap_or(&file.path)
+                .to_string_lossy()
+                .replace('\\', "/")
+                .replace(' ', "%20");
+            review.push_str(&format!(
+                "- [{}]({}) ({} words)\n",
+                date.format("%A, %B %d"),
+                link,
+                info.words
+            ));
+        }
+    }
+
+    review
+}
+
+/// Writes a review of the year into the `review` block of `{year}_journey.md`, leaving the rest of
+/// the file alone.
+pub fn year_review(year: u32) -> Result<()> {
+    let year = year as i32;
+    let journal_root = Path::new(&get_git_root()?).join("journal");
+    let year_folder = journal_root.join(year.to_string());
+    if !year_folder.exists() {
+        anyhow::bail!(
+            "No journal folder for {} (create it with `ja create-year {}`)",
+            year,
+            year
+        );
+    }
+
+    let entries: Vec<(JournalFile, EntryInfo)> = indexed_journal_files("journal")?
+        .into_iter()
+        .filter(|(file, _)| file.year == year)
+        .collect();
+
+    let mut happenings = Vec::new();
+    for month in 1..=12 {
+        let Some(date) = NaiveDate::from_ymd_opt(year, month, 1) else {
+            continue;
+        };
+        let month_name = date.format("%B").to_string();
+        let path = journal_root
+            .join(month_folder(date))
+            .join(format!("{} {} Happenings.md", month_name, year));
+        let contents = match fs::read_to_string(&path) {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == ErrorKind::NotFound => String::new(),
+            Err(e) => return Err(e).with_context(|| format!("Failed to read {}", path.display())),
+        };
+        happenings.push((month_name, highlights(&contents)));
+    }
+
+    let journey_path = year_folder.join(format!("{}_journey.md", year));
+    let review = review_markdown(year, &year_folder, &entries, &happenings);
+    let journey_path = journey_path.to_string_lossy();
+    if write_block(&journey_path, "review", &review)? {
+        println!("Updated the review in {}", journey_path);
+    } else {
+        println!("Review in {} is up to date", journey_path);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn entry(day: u32, words: u64, tags: &[&str]) -> (JournalFile, EntryInfo) {
+        let date = NaiveDate::from_ymd_opt(2024, 3, day).unwrap();
+        let file = JournalFile {
+            notebook: "journal".to_string(),
+            path: PathBuf::from("/notes/journal")
+                .join(month_folder(date))
+                .join(crate::notebook::entry_file_name(date)),
+            year: 2024,
+            month: 3,
+            day,
+            weekday: date.format("%A").to_string(),
+        };
+        let info = EntryInfo {
+            size: words * 5,
+            mtime_ns: 0,
+            hash: String::new(),
+            words,
+            lines: 1,
+            header: None,
+            tags: tags.iter().map(|t| t.to_string()).collect(),
+            fields: Default::default(),
+            links: Vec::new(),
+        };
+        (file, info)
+    }
+
+    #[test]
+    fn test_highlights() {
+        let contents = "# March 2024 Happenings\n\n- Moved apartments\n* [x] Finished thesis draft\n\n<!-- ja:summary:start -->\n- [Mar 05](05_Tuesday.md) Generated\n<!-- ja:summary:end -->\nSpring break trip\n- Fourth\n";
+        assert_eq!(
+            highlights(contents),
+            vec![
+                "Moved apartments",
+                "Finished thesis draft",
+                "Spring break trip"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_review_markdown() {
+        let entries = vec![
+            entry(1, 100, &["travel"]),
+            entry(2, 300, &["travel", "anna"]),
+            entry(3, 0, &[]),
+            entry(4, 200, &[]),
+        ];
+        let happenings = vec![
+            ("February".to_string(), vec![]),
+            ("March".to_string(), vec!["Moved apartments".to_string()]),
+        ];
+        let review = review_markdown(
+            2024,
+            Path::new("/notes/journal/2024"),
+            &entries,
+            &happenings,
+        );
+
+        assert!(review.starts_with("## 2024 in Review\n"));
+        assert!(review.contains("- Entries written: 3 of 4 days (75.0%)\n"));
+        assert!(review.contains("- Longest streak: 2 days (March 01 to March 02)\n"));
+        assert!(review.contains("- Words: 600 (200 per entry)\n"));
+        assert!(review.contains("- Most-used tags: #travel (2), #anna (1)\n"));
+        assert!(!review.contains("February"));
+        assert!(review.contains("**March**\n- Moved apartments\n"));
+        assert!(review.contains(
+            "### Longest Entries\n\n- [Saturday, March 02](03-mar/02_Saturday.md) (300 words)\n"
+        ));
+    }
+}
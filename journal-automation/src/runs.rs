@@ -0,0 +1,405 @@
+use crate::block::write_block;
+use crate::journal::get_journal_path_for_date;
+use crate::utils::get_git_root;
+use anyhow::{Context, Result};
+use chrono::{DateTime, Datelike, Local, NaiveDate, Utc};
+use quick_xml::{events::Event, Reader};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    fs,
+    io::BufRead,
+    path::{Path, PathBuf},
+};
+
+const METERS_PER_MILE: f64 = 1609.344;
+const FEET_PER_METER: f64 = 3.28084;
+const EARTH_RADIUS_METERS: f64 = 6_371_000.0;
+
+// Ignore elevation changes smaller than this to keep GPS noise out of the gain
+const ELEVATION_THRESHOLD_METERS: f64 = 2.0;
+
+#[derive(Debug, Default, Clone)]
+struct TrackPoint {
+    lat: Option<f64>,
+    lon: Option<f64>,
+    elevation: Option<f64>,
+    time: Option<DateTime<Utc>>,
+}
+
+/// A run summary as stored in the runs log.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Run {
+    pub start: DateTime<Utc>,
+    pub distance_m: f64,
+    pub duration_s: i64,
+    pub elevation_gain_m: f64,
+    pub source: String,
+}
+
+impl Run {
+    pub fn date(&self) -> NaiveDate {
+        self.start.with_timezone(&Local).date_naive()
+    }
+
+    pub fn miles(&self) -> f64 {
+        self.distance_m / METERS_PER_MILE
+    }
+
+    /// Minutes per mile
+    fn pace(&self) -> Option<f64> {
+        if self.distance_m > 0.0 {
+            Some(self.duration_s as f64 / 60.0 / self.miles())
+        } else {
+            None
+        }
+    }
+}
+
+fn format_duration(seconds: i64) -> String {
+    if seconds >= 3600 {
+        format!(
+            "{}:{:02}:{:02}",
+            seconds / 3600,
+            (seconds % 3600) / 60,
+            seconds % 60
+        )
+    } else {
+        format!("{}:{:02}", seconds / 60, seconds % 60)
+    }
+}
+
+fn format_pace(pace: Option<f64>) -> String {
+    match pace {
+        Some(pace) => {
+            let seconds = (pace * 60.0).round() as i64;
+            format!("{}:{:02} /mi", seconds / 60, seconds % 60)
+        }
+        None => "—".to_string(),
+    }
+}
+
+fn haversine(a: (f64, f64), b: (f64, f64)) -> f64 {
+    let (lat1, lon1) = (a.0.to_radians(), a.1.to_radians());
+    let (lat2, lon2) = (b.0.to_radians(), b.1.to_radians());
+    let h = ((lat2 - lat1) / 2.0).sin().powi(2)
+        + lat1.cos() * lat2.cos() * ((lon2 - lon1) / 2.0).sin().powi(2);
+    2.0 * EARTH_RADIUS_METERS * h.sqrt().asin()
+}
+
+/// Reads track points from a GPX (`trkpt`) or TCX (`Trackpoint`) file.
+fn parse_track<R: BufRead>(source: R) -> Result<Vec<TrackPoint>> {
+    let mut reader = Reader::from_reader(source);
+    let mut buf = Vec::new();
+    let mut points = Vec::new();
+    let mut current: Option<TrackPoint> = None;
+    let mut current_tag = String::new();
+
+    loop {
+        match reader.read_event_into(&mut buf)? {
+            Event::Eof => break,
+            Event::Start(element) => {
+                let name = String::from_utf8_lossy(element.local_name().as_ref()).into_owned();
+                if name == "trkpt" || name == "Trackpoint" {
+                    let mut point = TrackPoint::default();
+                    for attr in element.attributes() {
+                        let attr = attr?;
+                        let value = attr.unescape_value()?;
+                        match attr.key.local_name().as_ref() {
+                            b"lat" => point.lat = value.parse().ok(),
+                            b"lon" => point.lon = value.parse().ok(),
+                            _ => {}
+                        }
+                    }
+                    current = Some(point);
+                }
+                current_tag = name;
+            }
+            Event::Text(text) => {
+                if let Some(point) = current.as_mut() {
+                    let value = text.unescape()?;
+                    let value = value.trim();
+                    match current_tag.as_str() {
+                        "ele" | "AltitudeMeters" => point.elevation = value.parse().ok(),
+                        "time" | "Time" => {
+                            point.time = DateTime::parse_from_rfc3339(value)
+                                .ok()
+                                .map(|t| t.with_timezone(&Utc))
+                        }
+                        "LatitudeDegrees" => point.lat = value.parse().ok(),
+                        "LongitudeDegrees" => point.lon = value.parse().ok(),
+                        _ => {}
+                    }
+                }
+            }
+            Event::End(element) => {
+                let name = element.local_name();
+                if name.as_ref() == b"trkpt" || name.as_ref() == b"Trackpoint" {
+                    if let Some(point) = current.take() {
+                        points.push(point);
+                    }
+                }
+                current_tag.clear();
+            }
+            _ => {}
+        }
+        buf.clear();
+    }
+
+    Ok(points)
+}
+
+fn summarize(points: &[TrackPoint], source: &str) -> Option<Run> {
+    let times: Vec<DateTime<Utc>> = points.iter().filter_map(|p| p.time).collect();
+    let start = *times.iter().min()?;
+    let end = *times.iter().max()?;
+
+    let positions: Vec<(f64, f64)> = points
+        .iter()
+        .filter_map(|p| Some((p.lat?, p.lon?)))
+        .collect();
+    let distance_m = positions.windows(2).map(|w| haversine(w[0], w[1])).sum();
+
+    let mut elevation_gain_m = 0.0;
+    let mut reference: Option<f64> = None;
+    for elevation in points.iter().filter_map(|p| p.elevation) {
+        match reference {
+            None => reference = Some(elevation),
+            Some(r) if elevation - r >= ELEVATION_THRESHOLD_METERS => {
+                elevation_gain_m += elevation - r;
+                reference = Some(elevation);
+            }
+            Some(r) if r - elevation >= ELEVATION_THRESHOLD_METERS => {
+                reference = Some(elevation);
+            }
+            _ => {}
+        }
+    }
+
+    Some(Run {
+        start,
+        distance_m,
+        duration_s: (end - start).num_seconds(),
+        elevation_gain_m,
+        source: source.to_string(),
+    })
+}
+
+// Folder inside journal-automation that holds the run history
+pub const DATA_DIR: &str = "runs";
+
+pub fn runs_log_path() -> Result<String> {
+    Ok(format!(
+        "{}/journal-automation/{}/runs.csv",
+        get_git_root()?,
+        DATA_DIR
+    ))
+}
+
+pub fn load_runs() -> Result<Vec<Run>> {
+    let path = runs_log_path()?;
+    if !Path::new(&path).exists() {
+        return Ok(Vec::new());
+    }
+
+    let mut reader = csv::Reader::from_path(&path)
+        .with_context(|| format!("Failed to read runs log: {}", path))?;
+    let mut runs = Vec::new();
+    for record in reader.deserialize() {
+        runs.push(record?);
+    }
+    Ok(runs)
+}
+
+fn save_runs(runs: &[Run]) -> Result<()> {
+    let path = runs_log_path()?;
+    if let Some(parent) = Path::new(&path).parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    let mut writer = csv::Writer::from_path(&path)
+        .with_context(|| format!("Failed to write runs log: {}", path))?;
+    for run in runs {
+        writer.serialize(run)?;
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+fn runs_table(runs: &[&Run]) -> String {
+    let mut table = String::from(
+        "| run | distance | time | pace | elevation |\n| --- | -------- | ---- | ---- | --------- |",
+    );
+    for run in runs {
+        table.push_str(&format!(
+            "\n| 🏃 {} | {:.2} mi | {} | {} | {:.0} ft |",
+            run.start.with_timezone(&Local).format("%H:%M"),
+            run.miles(),
+            format_duration(run.duration_s),
+            format_pace(run.pace()),
+            run.elevation_gain_m * FEET_PER_METER
+        ));
+    }
+    table
+}
+
+fn year_summary(year: i32, runs: &[&Run]) -> String {
+    let distance_m: f64 = runs.iter().map(|r| r.distance_m).sum();
+    let duration_s: i64 = runs.iter().map(|r| r.duration_s).sum();
+    let elevation_m: f64 = runs.iter().map(|r| r.elevation_gain_m).sum();
+
+    let mut summary = format!(
+        "## Running {}\n\n- Runs: {}\n- Distance: {:.1} mi\n- Time: {}\n- Elevation gain: {:.0} ft\n",
+        year,
+        runs.len(),
+        distance_m / METERS_PER_MILE,
+        format_duration(duration_s),
+        elevation_m * FEET_PER_METER
+    );
+
+    let mut by_month: BTreeMap<u32, (u32, f64)> = BTreeMap::new();
+    for run in runs {
+        let month = by_month.entry(run.date().month()).or_default();
+        month.0 += 1;
+        month.1 += run.miles();
+    }
+    summary.push_str("\n| month | runs | distance |\n| ----- | ---- | -------- |");
+    for (month, (count, miles)) in by_month {
+        let month_name = NaiveDate::from_ymd_opt(year, month, 1)
+            .map(|d| d.format("%B").to_string())
+            .unwrap_or_default();
+        summary.push_str(&format!(
+            "\n| {} | {} | {:.1} mi |",
+            month_name, count, miles
+        ));
+    }
+
+    summary
+}
+
+pub fn import_runs(dir: &str) -> Result<()> {
+    let mut files: Vec<PathBuf> = walkdir::WalkDir::new(dir)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .map(|e| e.path().to_path_buf())
+        .filter(|p| {
+            p.extension()
+                .and_then(|ext| ext.to_str())
+                .is_some_and(|ext| {
+                    ext.eq_ignore_ascii_case("gpx") || ext.eq_ignore_ascii_case("tcx")
+                })
+        })
+        .collect();
+    files.sort();
+
+    // Merge into the existing log, keyed by start time so re-imports don't duplicate runs
+    let mut runs: BTreeMap<DateTime<Utc>, Run> =
+        load_runs()?.into_iter().map(|r| (r.start, r)).collect();
+    let mut imported = Vec::new();
+    for file in &files {
+        let source =
+            fs::File::open(file).with_context(|| format!("Failed to open {}", file.display()))?;
+        let points = parse_track(std::io::BufReader::new(source))
+            .with_context(|| format!("Failed to parse {}", file.display()))?;
+        let name = file
+            .file_name()
+            .map(|f| f.to_string_lossy().to_string())
+            .unwrap_or_default();
+        match summarize(&points, &name) {
+            Some(run) => {
+                imported.push(run.date());
+                runs.insert(run.start, run);
+            }
+            None => println!("Skipping {}: no timestamped track points", name),
+        }
+    }
+    let runs: Vec<Run> = runs.into_values().collect();
+    save_runs(&runs)?;
+
+    // Update the daily entries that had runs imported
+    let mut by_day: BTreeMap<NaiveDate, Vec<&Run>> = BTreeMap::new();
+    for run in &runs {
+        by_day.entry(run.date()).or_default().push(run);
+    }
+    let mut updated = 0;
+    for date in &imported {
+        let journal_path = get_journal_path_for_date(*date, "journal")?;
+        let contents = fs::read_to_string(&journal_path).unwrap_or_default();
+
+        // Don't turn an empty placeholder into a "completed" day
+        if contents.trim().is_empty() {
+            continue;
+        }
+        if write_block(&journal_path, "runs", &runs_table(&by_day[date]))? {
+            updated += 1;
+        }
+    }
+
+    // Refresh the yearly totals in each affected journey file
+    let git_root = get_git_root()?;
+    let mut years: Vec<i32> = imported.iter().map(|d| d.year()).collect();
+    years.sort();
+    years.dedup();
+    for year in years {
+        let year_folder = format!("{}/journal/{}", git_root, year);
+        if !Path::new(&year_folder).exists() {
+            continue;
+        }
+        let year_runs: Vec<&Run> = runs.iter().filter(|r| r.date().year() == year).collect();
+        write_block(
+            &format!("{}/{}_journey.md", year_folder, year),
+            "running",
+            &year_summary(year, &year_runs),
+        )?;
+    }
+
+    println!(
+        "Imported {} runs from {} files ({} entries updated)",
+        imported.len(),
+        files.len(),
+        updated
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_gpx() {
+        let gpx = r#"<?xml version="1.0" encoding="UTF-8"?>
+<gpx version="1.1" creator="Nike Run Club" xmlns="http://www.topografix.com/GPX/1/1">
+ <trk><trkseg>
+  <trkpt lat="40.0000" lon="-88.0000"><ele>200.0</ele><time>2024-03-15T22:00:00Z</time></trkpt>
+  <trkpt lat="40.0100" lon="-88.0000"><ele>205.0</ele><time>2024-03-15T22:05:00Z</time></trkpt>
+  <trkpt lat="40.0200" lon="-88.0000"><ele>204.0</ele><time>2024-03-15T22:10:00Z</time></trkpt>
+ </trkseg></trk>
+</gpx>"#;
+
+        let points = parse_track(gpx.as_bytes()).unwrap();
+        assert_eq!(points.len(), 3);
+
+        let run = summarize(&points, "run.gpx").unwrap();
+        assert_eq!(run.duration_s, 600);
+        assert!((run.distance_m - 2223.9).abs() < 1.0);
+        assert_eq!(run.elevation_gain_m, 5.0);
+    }
+
+    #[test]
+    fn test_parse_tcx() {
+        let tcx = r#"<?xml version="1.0" encoding="UTF-8"?>
+<TrainingCenterDatabase xmlns="http://www.garmin.com/xmlschemas/TrainingCenterDatabase/v2">
+ <Activities><Activity Sport="Running"><Lap><Track>
+  <Trackpoint><Time>2024-03-15T22:00:00Z</Time><Position><LatitudeDegrees>40.0</LatitudeDegrees><LongitudeDegrees>-88.0</LongitudeDegrees></Position><AltitudeMeters>200</AltitudeMeters></Trackpoint>
+  <Trackpoint><Time>2024-03-15T22:08:00Z</Time><Position><LatitudeDegrees>40.01</LatitudeDegrees><LongitudeDegrees>-88.0</LongitudeDegrees></Position><AltitudeMeters>210</AltitudeMeters></Trackpoint>
+ </Track></Lap></Activity></Activities>
+</TrainingCenterDatabase>"#;
+
+        let points = parse_track(tcx.as_bytes()).unwrap();
+        let run = summarize(&points, "run.tcx").unwrap();
+        assert_eq!(run.duration_s, 480);
+        assert!((run.miles() - 0.69).abs() < 0.01);
+        assert_eq!(run.elevation_gain_m, 10.0);
+    }
+}
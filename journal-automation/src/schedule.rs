@@ -1,24 +1,247 @@
+use crate::utils::get_git_root;
 use anyhow::{Context, Result};
-use chrono::{Datelike, NaiveDate};
+use chrono::{Datelike, NaiveDate, NaiveTime};
 use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
 use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct ClassSchedule {
     pub class_name: String,
+    /// Term this class belongs to; its dates and breaks fill in for the ones left out here
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub term: Option<String>,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
     pub start_date: String, // YYYY-MM-DD
-    pub end_date: String,   // YYYY-MM-DD
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub end_date: String, // YYYY-MM-DD
     pub schedule: Vec<ClassDay>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub exclusions: Vec<Exclusion>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub extra_days: Vec<ExtraDay>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub overrides: Vec<DateOverride>,
 }
 
+/// One weekly meeting. A weekday can have several (e.g. a lecture and a lab), and a meeting can
+/// repeat every few weeks instead of every week.
 #[derive(Debug, Serialize, Deserialize)]
 pub struct ClassDay {
     pub weekday: Weekday,
     pub start_time: String, // HH:MM in 24-hour format
     pub end_time: String,   // HH:MM in 24-hour format
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub location: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub instructor: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub section: Option<String>, // e.g. Lecture, Lab, Discussion
+    #[serde(
+        default = "default_week_interval",
+        skip_serializing_if = "is_every_week"
+    )]
+    pub week_interval: u32, // 2 for every other week
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub week_offset: u32, // which week of the interval, counted from the term's first week
+    /// Dates only this meeting is cancelled, e.g. a lab that doesn't meet while the lecture does
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub exclusions: Vec<Exclusion>,
+}
+
+fn default_week_interval() -> u32 {
+    1
+}
+
+fn is_every_week(interval: &u32) -> bool {
+    *interval == 1
+}
+
+fn is_zero(offset: &u32) -> bool {
+    *offset == 0
+}
+
+/// Days without class: either a single `"YYYY-MM-DD"` or a range such as spring break.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Exclusion {
+    Date(String), // YYYY-MM-DD
+    Range {
+        start: String, // YYYY-MM-DD
+        end: String,   // YYYY-MM-DD
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        reason: Option<String>,
+    },
+}
+
+/// A one-off session outside the weekly pattern, such as a makeup class.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ExtraDay {
+    pub date: String,       // YYYY-MM-DD
+    pub start_time: String, // HH:MM in 24-hour format
+    pub end_time: String,   // HH:MM in 24-hour format
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub location: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub instructor: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub section: Option<String>,
+}
+
+/// Changes to the sessions (regular or extra) on one date, e.g. a different room for an exam. With a
+/// `section`, only sessions of that section are changed.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DateOverride {
+    pub date: String, // YYYY-MM-DD
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub section: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub start_time: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub end_time: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub location: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub instructor: Option<String>,
+}
+
+/// A class meeting on a specific date, after exclusions and overrides have been applied.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Session {
+    pub date: NaiveDate,
+    pub start_time: String,
+    pub end_time: String,
     pub location: Option<String>,
     pub instructor: Option<String>,
+    pub section: Option<String>,
+}
+
+/// A schedule with its dates parsed once, so the sessions of many days can be looked up without
+/// re-reading exclusions and overrides for each one.
+pub struct ResolvedSchedule<'a> {
+    schedule: &'a ClassSchedule,
+    start_date: NaiveDate,
+    end_date: NaiveDate,
+    excluded: HashSet<NaiveDate>,
+    meeting_exclusions: Vec<HashSet<NaiveDate>>, // one set per entry in `schedule.schedule`
+    overrides: Vec<(NaiveDate, &'a DateOverride)>,
+    extra_days: Vec<(NaiveDate, &'a ExtraDay)>,
+}
+
+/// A semester such as "Spring 2024". Schedules join a term with `"term": "Spring 2024"` and
+/// take its dates and breaks.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Term {
+    pub name: String,
+    pub start_date: String, // YYYY-MM-DD
+    pub end_date: String,   // YYYY-MM-DD
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub breaks: Vec<Exclusion>,
+}
+
+impl Term {
+    pub fn contains(&self, date: NaiveDate) -> bool {
+        let date = date.format("%Y-%m-%d").to_string();
+        self.start_date <= date && date <= self.end_date
+    }
+}
+
+/// Terms live in `terms/terms.json`, next to the `schedules` folder.
+pub fn terms_path_for(schedules_dir: &Path) -> PathBuf {
+    schedules_dir
+        .parent()
+        .unwrap_or(schedules_dir)
+        .join("terms/terms.json")
+}
+
+pub fn load_terms(path: &Path) -> Result<Vec<Term>> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let contents = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read terms: {}", path.display()))?;
+    serde_json::from_str(&contents)
+        .with_context(|| format!("Failed to parse terms: {}", path.display()))
+}
+
+pub fn get_schedules_dir() -> Result<String> {
+    Ok(format!("{}/journal-automation/schedules", get_git_root()?))
+}
+
+pub fn get_schedule_path(class: &str) -> Result<String> {
+    Ok(format!("{}/{}.json", get_schedules_dir()?, class))
+}
+
+/// Lists the schedule files in `schedules/`, sorted by file name.
+pub fn list_schedule_files() -> Result<Vec<PathBuf>> {
+    let mut paths: Vec<PathBuf> = std::fs::read_dir(get_schedules_dir()?)
+        .context("Failed to read schedules folder")?
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .filter(|p| p.extension().is_some_and(|ext| ext == "json"))
+        .collect();
+    paths.sort();
+    Ok(paths)
+}
+
+/// Loads every schedule with its term applied, skipping (and reporting) any that fail to load.
+pub fn load_schedules() -> Result<Vec<ClassSchedule>> {
+    let mut schedules = Vec::new();
+    for path in list_schedule_files()? {
+        match ClassSchedule::from_file(&path.to_string_lossy()) {
+            Ok(schedule) => schedules.push(schedule),
+            Err(e) => println!("Skipping {}: {:#}", path.display(), e),
+        }
+    }
+    Ok(schedules)
+}
+
+fn parse_date(value: &str, what: &str) -> Result<NaiveDate> {
+    NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .with_context(|| format!("Invalid {} format: {}", what, value))
+}
+
+/// Parses a strict 24-hour `HH:MM` time, rejecting forms like `9:00` or `10:00am`.
+pub fn parse_time(value: &str) -> Result<NaiveTime> {
+    let bytes = value.as_bytes();
+    let well_formed = bytes.len() == 5
+        && bytes[2] == b':'
+        && [0, 1, 3, 4].iter().all(|&i| bytes[i].is_ascii_digit());
+    if !well_formed {
+        anyhow::bail!("Invalid time format: {} (expected HH:MM)", value);
+    }
+    NaiveTime::parse_from_str(value, "%H:%M").with_context(|| format!("Invalid time: {}", value))
+}
+
+/// Counts whole weeks (Monday to Sunday) between the term's first week and the week of `date`.
+pub fn week_number(term_start: NaiveDate, date: NaiveDate) -> i64 {
+    let monday =
+        |d: NaiveDate| d - chrono::Duration::days(d.weekday().num_days_from_monday() as i64);
+    (monday(date) - monday(term_start)).num_weeks()
+}
+
+impl ClassDay {
+    /// Whether this meeting happens in the given week of the term.
+    pub fn meets_in_week(&self, week: i64) -> bool {
+        let interval = self.week_interval.max(1) as i64;
+        week.rem_euclid(interval) == self.week_offset as i64 % interval
+    }
+}
+
+impl Exclusion {
+    pub fn dates(&self) -> Result<Vec<NaiveDate>> {
+        match self {
+            Exclusion::Date(date) => Ok(vec![parse_date(date, "exclusion date")?]),
+            Exclusion::Range { start, end, .. } => {
+                let start = parse_date(start, "exclusion start date")?;
+                let end = parse_date(end, "exclusion end date")?;
+                Ok(start.iter_days().take_while(|d| *d <= end).collect())
+            }
+        }
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Hash, Clone)]
@@ -47,51 +270,310 @@ impl fmt::Display for Weekday {
     }
 }
 
+impl From<chrono::Weekday> for Weekday {
+    fn from(weekday: chrono::Weekday) -> Self {
+        match weekday {
+            chrono::Weekday::Mon => Weekday::Monday,
+            chrono::Weekday::Tue => Weekday::Tuesday,
+            chrono::Weekday::Wed => Weekday::Wednesday,
+            chrono::Weekday::Thu => Weekday::Thursday,
+            chrono::Weekday::Fri => Weekday::Friday,
+            chrono::Weekday::Sat => Weekday::Saturday,
+            chrono::Weekday::Sun => Weekday::Sunday,
+        }
+    }
+}
+
 impl ClassSchedule {
+    /// Loads a schedule with its term's dates and breaks applied.
     pub fn from_file(path: &str) -> Result<Self> {
+        let mut schedule = Self::read_file(path)?;
+        if let Some(term_name) = &schedule.term {
+            let schedules_dir = Path::new(path).parent().unwrap_or(Path::new("."));
+            let terms = load_terms(&terms_path_for(schedules_dir))?;
+            let term = terms
+                .iter()
+                .find(|t| &t.name == term_name)
+                .with_context(|| format!("Unknown term {} in {}", term_name, path))?;
+            schedule.apply_term(term);
+        }
+        Ok(schedule)
+    }
+
+    /// Loads a schedule exactly as written, without filling in anything from its term.
+    pub fn read_file(path: &str) -> Result<Self> {
         let contents = std::fs::read_to_string(path)
             .with_context(|| format!("Failed to read schedule file: {}", path))?;
         serde_json::from_str(&contents)
             .with_context(|| format!("Failed to parse schedule file: {}", path))
     }
 
+    /// Uses the term's dates where the schedule has none and adds the term's breaks.
+    pub fn apply_term(&mut self, term: &Term) {
+        if self.start_date.is_empty() {
+            self.start_date = term.start_date.clone();
+        }
+        if self.end_date.is_empty() {
+            self.end_date = term.end_date.clone();
+        }
+        for term_break in &term.breaks {
+            if !self.exclusions.contains(term_break) {
+                self.exclusions.push(term_break.clone());
+            }
+        }
+    }
+
     pub fn get_class_dates(&self) -> Result<Vec<NaiveDate>> {
-        let start_date = NaiveDate::parse_from_str(&self.start_date, "%Y-%m-%d")
-            .with_context(|| format!("Invalid start date format: {}", self.start_date))?;
-        let end_date = NaiveDate::parse_from_str(&self.end_date, "%Y-%m-%d")
-            .with_context(|| format!("Invalid end date format: {}", self.end_date))?;
-
-        // Create a set of weekdays that have classes
-        let class_weekdays: HashSet<Weekday> = self
-            .schedule
-            .iter()
-            .map(|day| day.weekday.clone())
+        let resolved = self.resolve()?;
+
+        // Extra days can fall outside the regular term
+        let mut candidates: Vec<NaiveDate> = resolved
+            .start_date
+            .iter_days()
+            .take_while(|d| *d <= resolved.end_date)
             .collect();
+        candidates.extend(resolved.extra_days.iter().map(|(date, _)| *date));
+        candidates.sort();
+        candidates.dedup();
+
+        Ok(candidates
+            .into_iter()
+            .filter(|date| !resolved.sessions_on(*date).is_empty())
+            .collect())
+    }
+
+    pub fn excluded_dates(&self) -> Result<HashSet<NaiveDate>> {
+        let mut dates = HashSet::new();
+        for exclusion in &self.exclusions {
+            dates.extend(exclusion.dates()?);
+        }
+        Ok(dates)
+    }
+
+    /// Parses the term dates, exclusions, overrides and extra days for repeated lookups.
+    pub fn resolve(&self) -> Result<ResolvedSchedule<'_>> {
+        let mut meeting_exclusions = Vec::new();
+        for day in &self.schedule {
+            let mut dates = HashSet::new();
+            for exclusion in &day.exclusions {
+                dates.extend(exclusion.dates()?);
+            }
+            meeting_exclusions.push(dates);
+        }
+        let mut overrides = Vec::new();
+        for date_override in &self.overrides {
+            overrides.push((
+                parse_date(&date_override.date, "override date")?,
+                date_override,
+            ));
+        }
+        let mut extra_days = Vec::new();
+        for extra in &self.extra_days {
+            extra_days.push((parse_date(&extra.date, "extra day date")?, extra));
+        }
+
+        Ok(ResolvedSchedule {
+            schedule: self,
+            start_date: parse_date(&self.start_date, "start date")?,
+            end_date: parse_date(&self.end_date, "end date")?,
+            excluded: self.excluded_dates()?,
+            meeting_exclusions,
+            overrides,
+            extra_days,
+        })
+    }
 
-        let mut class_dates = Vec::new();
-        let mut current_date = start_date;
-
-        while current_date <= end_date {
-            let weekday = match current_date.weekday() {
-                chrono::Weekday::Mon => Weekday::Monday,
-                chrono::Weekday::Tue => Weekday::Tuesday,
-                chrono::Weekday::Wed => Weekday::Wednesday,
-                chrono::Weekday::Thu => Weekday::Thursday,
-                chrono::Weekday::Fri => Weekday::Friday,
-                chrono::Weekday::Sat => Weekday::Saturday,
-                chrono::Weekday::Sun => Weekday::Sunday,
+    /// Checks dates and times without stopping at the first problem. Returns one message per issue.
+    pub fn validate(&self) -> Vec<String> {
+        let mut issues = Vec::new();
+
+        let start_date = parse_date(&self.start_date, "start date");
+        let end_date = parse_date(&self.end_date, "end date");
+        match (&start_date, &end_date) {
+            (Ok(start), Ok(end)) if start > end => issues.push(format!(
+                "Start date {} is after end date {}",
+                self.start_date, self.end_date
+            )),
+            _ => {}
+        }
+        for result in [start_date, end_date] {
+            if let Err(e) = result {
+                issues.push(e.to_string());
+            }
+        }
+
+        for day in &self.schedule {
+            if day.week_interval == 0 {
+                issues.push(format!("{}: week interval must be at least 1", day.weekday));
+            } else if day.week_offset >= day.week_interval {
+                issues.push(format!(
+                    "{}: week offset {} must be less than the week interval {}",
+                    day.weekday, day.week_offset, day.week_interval
+                ));
+            }
+        }
+
+        let mut check_times =
+            |label: String, start: &str, end: &str| match (parse_time(start), parse_time(end)) {
+                (Ok(start_time), Ok(end_time)) if start_time >= end_time => issues.push(format!(
+                    "{}: start time {} is not before end time {}",
+                    label, start, end
+                )),
+                (Err(e), _) | (_, Err(e)) => issues.push(format!("{}: {}", label, e)),
+                _ => {}
             };
+        for day in &self.schedule {
+            check_times(day.weekday.to_string(), &day.start_time, &day.end_time);
+        }
+        for extra in &self.extra_days {
+            check_times(
+                format!("Extra day {}", extra.date),
+                &extra.start_time,
+                &extra.end_time,
+            );
+        }
+        // An override that only moves one end is checked against each session it actually
+        // changes (bad dates are reported below, so there is nothing to check then)
+        let resolved = self.resolve().ok();
+        for date_override in &self.overrides {
+            let label = format!("Override {}", date_override.date);
+            match (&date_override.start_time, &date_override.end_time) {
+                (Some(start_time), Some(end_time)) => check_times(label, start_time, end_time),
+                (None, None) => {}
+                _ => {
+                    let (Some(resolved), Ok(date)) =
+                        (&resolved, parse_date(&date_override.date, "override date"))
+                    else {
+                        continue;
+                    };
+                    for session in resolved.sessions_on(date).iter().filter(|s| {
+                        date_override.section.is_none() || s.section == date_override.section
+                    }) {
+                        check_times(label.clone(), &session.start_time, &session.end_time);
+                    }
+                }
+            }
+        }
+
+        let meeting_exclusions = self.schedule.iter().flat_map(|d| &d.exclusions);
+        for exclusion in self.exclusions.iter().chain(meeting_exclusions) {
+            match exclusion {
+                Exclusion::Range { start, end, .. } => {
+                    match (
+                        parse_date(start, "exclusion start date"),
+                        parse_date(end, "exclusion end date"),
+                    ) {
+                        (Ok(s), Ok(e)) if s > e => issues.push(format!(
+                            "Exclusion range starts after it ends: {} to {}",
+                            start, end
+                        )),
+                        (Err(e), _) | (_, Err(e)) => issues.push(e.to_string()),
+                        _ => {}
+                    }
+                }
+                Exclusion::Date(date) => {
+                    if let Err(e) = parse_date(date, "exclusion date") {
+                        issues.push(e.to_string());
+                    }
+                }
+            }
+        }
+        for date in self
+            .extra_days
+            .iter()
+            .map(|e| (&e.date, "extra day date"))
+            .chain(self.overrides.iter().map(|o| (&o.date, "override date")))
+        {
+            if let Err(e) = parse_date(date.0, date.1) {
+                issues.push(e.to_string());
+            }
+        }
+
+        issues
+    }
+
+    /// Returns the sessions held on a date, with exclusions, overrides and extra days applied.
+    /// Use `resolve` when looking up many dates.
+    pub fn sessions_on(&self, date: NaiveDate) -> Result<Vec<Session>> {
+        Ok(self.resolve()?.sessions_on(date))
+    }
+}
+
+impl ResolvedSchedule<'_> {
+    /// Returns the sessions held on a date, with exclusions, overrides and extra days applied.
+    pub fn sessions_on(&self, date: NaiveDate) -> Vec<Session> {
+        let mut sessions = Vec::new();
 
-            if class_weekdays.contains(&weekday) {
-                class_dates.push(current_date);
+        if date >= self.start_date && date <= self.end_date && !self.excluded.contains(&date) {
+            let weekday = Weekday::from(date.weekday());
+            let week = week_number(self.start_date, date);
+            for (day, excluded) in self.schedule.schedule.iter().zip(&self.meeting_exclusions) {
+                if day.weekday != weekday || !day.meets_in_week(week) || excluded.contains(&date) {
+                    continue;
+                }
+                sessions.push(Session {
+                    date,
+                    start_time: day.start_time.clone(),
+                    end_time: day.end_time.clone(),
+                    location: day.location.clone(),
+                    instructor: day.instructor.clone(),
+                    section: day.section.clone(),
+                });
             }
+        }
+
+        for (_, extra) in self.extra_days.iter().filter(|(d, _)| *d == date) {
+            sessions.push(Session {
+                date,
+                start_time: extra.start_time.clone(),
+                end_time: extra.end_time.clone(),
+                location: extra.location.clone(),
+                instructor: extra.instructor.clone(),
+                section: extra.section.clone(),
+            });
+        }
 
-            current_date = current_date
-                .succ_opt()
-                .ok_or_else(|| anyhow::anyhow!("Failed to get next date"))?;
+        for (_, date_override) in self.overrides.iter().filter(|(d, _)| *d == date) {
+            for session in sessions
+                .iter_mut()
+                .filter(|s| date_override.section.is_none() || s.section == date_override.section)
+            {
+                if let Some(start_time) = &date_override.start_time {
+                    session.start_time = start_time.clone();
+                }
+                if let Some(end_time) = &date_override.end_time {
+                    session.end_time = end_time.clone();
+                }
+                if let Some(location) = &date_override.location {
+                    session.location = Some(location.clone());
+                }
+                if let Some(instructor) = &date_override.instructor {
+                    session.instructor = Some(instructor.clone());
+                }
+            }
         }
 
-        Ok(class_dates)
+        sessions.sort_by(|a, b| a.start_time.cmp(&b.start_time));
+        sessions
+    }
+}
+
+/// Guesses the building from a location such as "Math Building 302" or
+/// "Siebel Center, Room 1404". Returns `None` when only a room is known.
+pub fn building(location: &str) -> Option<String> {
+    let name = location.split(',').next().unwrap_or(location);
+    let mut words: Vec<&str> = name.split_whitespace().collect();
+    while words
+        .last()
+        .is_some_and(|w| w.chars().any(|c| c.is_ascii_digit()) || w.eq_ignore_ascii_case("room"))
+    {
+        words.pop();
+    }
+
+    if words.is_empty() {
+        None
+    } else {
+        Some(words.join(" "))
     }
 }
 
@@ -157,4 +639,169 @@ mod tests {
         assert_eq!(dates[1], NaiveDate::from_ymd_opt(2024, 1, 17).unwrap());
         assert_eq!(dates[2], NaiveDate::from_ymd_opt(2024, 1, 22).unwrap());
     }
+
+    #[test]
+    fn test_validate() {
+        let json = r#"{
+            "class_name": "CS101",
+            "start_date": "2024-05-15",
+            "end_date": "2024-01-15",
+            "schedule": [
+                { "weekday": "monday", "start_time": "9:00", "end_time": "10:00" },
+                { "weekday": "tuesday", "start_time": "11:00", "end_time": "10:30" },
+                { "weekday": "friday", "start_time": "10:00", "end_time": "11:00" }
+            ]
+        }"#;
+
+        let schedule: ClassSchedule = serde_json::from_str(json).unwrap();
+        let issues = schedule.validate();
+        assert_eq!(issues.len(), 3);
+        assert!(issues[0].contains("is after end date"));
+        assert!(issues[1].contains("Invalid time format: 9:00"));
+        assert!(issues[2].contains("11:00 is not before end time 10:30"));
+
+        assert_eq!(
+            building("Math Building 302").as_deref(),
+            Some("Math Building")
+        );
+        assert_eq!(
+            building("Siebel Center, Room 1404").as_deref(),
+            Some("Siebel Center")
+        );
+        assert_eq!(building("Room 101"), None);
+    }
+
+    #[test]
+    fn test_exclusions_extra_days_and_overrides() {
+        let json = r#"{
+            "class_name": "CS101",
+            "start_date": "2024-03-04",
+            "end_date": "2024-03-20",
+            "schedule": [
+                {
+                    "weekday": "monday",
+                    "start_time": "10:00",
+                    "end_time": "11:30",
+                    "location": "Room 101"
+                }
+            ],
+            "exclusions": [
+                "2024-03-04",
+                { "start": "2024-03-09", "end": "2024-03-17", "reason": "Spring break" }
+            ],
+            "extra_days": [
+                { "date": "2024-03-22", "start_time": "14:00", "end_time": "15:00" }
+            ],
+            "overrides": [
+                { "date": "2024-03-18", "location": "Exam Hall" }
+            ]
+        }"#;
+
+        let schedule: ClassSchedule = serde_json::from_str(json).unwrap();
+        let dates = schedule.get_class_dates().unwrap();
+
+        // Mar 4 is excluded, Mar 11 falls in spring break, Mar 22 is a makeup session
+        assert_eq!(
+            dates,
+            vec![
+                NaiveDate::from_ymd_opt(2024, 3, 18).unwrap(),
+                NaiveDate::from_ymd_opt(2024, 3, 22).unwrap(),
+            ]
+        );
+
+        let sessions = schedule.sessions_on(dates[0]).unwrap();
+        assert_eq!(sessions.len(), 1);
+        assert_eq!(sessions[0].location.as_deref(), Some("Exam Hall"));
+        assert_eq!(sessions[0].start_time, "10:00");
+    }
+
+    #[test]
+    fn test_sections_and_alternating_weeks() {
+        let json = r#"{
+            "class_name": "CHEM101",
+            "start_date": "2024-01-17",
+            "end_date": "2024-02-02",
+            "schedule": [
+                { "weekday": "friday", "start_time": "09:00", "end_time": "10:00", "section": "Lecture" },
+                {
+                    "weekday": "friday",
+                    "start_time": "13:00",
+                    "end_time": "16:00",
+                    "section": "Lab",
+                    "week_interval": 2,
+                    "week_offset": 1
+                }
+            ],
+            "overrides": [
+                { "date": "2024-01-26", "section": "Lab", "location": "Lab B" }
+            ]
+        }"#;
+
+        let schedule: ClassSchedule = serde_json::from_str(json).unwrap();
+        assert!(schedule.validate().is_empty());
+
+        // The term starts on a Wednesday, so the lab is in the weeks of Jan 22 and Feb 5
+        let first = schedule
+            .sessions_on(NaiveDate::from_ymd_opt(2024, 1, 19).unwrap())
+            .unwrap();
+        assert_eq!(first.len(), 1);
+        assert_eq!(first[0].section.as_deref(), Some("Lecture"));
+
+        let second = schedule
+            .sessions_on(NaiveDate::from_ymd_opt(2024, 1, 26).unwrap())
+            .unwrap();
+        assert_eq!(second.len(), 2);
+        assert_eq!(second[0].location, None);
+        assert_eq!(second[1].section.as_deref(), Some("Lab"));
+        assert_eq!(second[1].location.as_deref(), Some("Lab B"));
+
+        let mut invalid: ClassSchedule = serde_json::from_str(json).unwrap();
+        invalid.schedule[1].week_offset = 2;
+        assert_eq!(invalid.validate().len(), 1);
+    }
+
+    #[test]
+    fn test_validate_partial_override() {
+        let json = r#"{
+            "class_name": "CS101",
+            "start_date": "2024-03-04",
+            "end_date": "2024-03-29",
+            "schedule": [
+                { "weekday": "monday", "start_time": "10:00", "end_time": "11:00" },
+                { "weekday": "monday", "start_time": "08:00", "end_time": "08:50", "section": "Lab" },
+                {
+                    "weekday": "wednesday",
+                    "start_time": "14:00",
+                    "end_time": "16:00",
+                    "section": "Studio",
+                    "week_interval": 2,
+                    "exclusions": ["2024-03-20"]
+                }
+            ],
+            "extra_days": [
+                { "date": "2024-03-09", "start_time": "10:00", "end_time": "12:00", "section": "Review" }
+            ],
+            "overrides": [
+                { "date": "2024-03-11", "end_time": "09:00" },
+                { "date": "2024-03-18", "section": "Lab", "end_time": "09:00" },
+                { "date": "2024-03-25", "start_time": "11:30" },
+                { "date": "2024-03-13", "section": "Studio", "end_time": "13:00" },
+                { "date": "2024-03-20", "section": "Studio", "end_time": "13:00" },
+                { "date": "2024-03-09", "section": "Review", "end_time": "09:00" }
+            ]
+        }"#;
+
+        // The Studio doesn't meet on the 13th (off week) or the 20th (excluded), so those
+        // overrides change nothing; the one moving the review session is checked
+        let schedule: ClassSchedule = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            schedule.validate(),
+            vec![
+                "Override 2024-03-11: start time 10:00 is not before end time 09:00",
+                "Override 2024-03-25: start time 11:30 is not before end time 11:00",
+                "Override 2024-03-25: start time 11:30 is not before end time 08:50",
+                "Override 2024-03-09: start time 10:00 is not before end time 09:00",
+            ]
+        );
+    }
 }
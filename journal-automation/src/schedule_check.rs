@@ -0,0 +1,301 @@
+use crate::schedule::{building, list_schedule_files, parse_time, ClassSchedule};
+use anyhow::Result;
+use chrono::{NaiveDate, NaiveTime};
+use std::collections::BTreeMap;
+
+#[derive(Debug)]
+struct ScheduledSession {
+    class_name: String,
+    start: NaiveTime,
+    end: NaiveTime,
+    location: Option<String>,
+}
+
+/// Collects one message per recurring problem, remembering the first date and how often it happens.
+#[derive(Default)]
+struct Findings {
+    issues: BTreeMap<String, (NaiveDate, u32)>,
+}
+
+impl Findings {
+    fn add(&mut self, message: String, date: NaiveDate) {
+        let entry = self.issues.entry(message).or_insert((date, 0));
+        entry.0 = entry.0.min(date);
+        entry.1 += 1;
+    }
+
+    fn print(&self) {
+        for (message, (first_date, count)) in &self.issues {
+            if *count == 1 {
+                println!("  {} on {}", message, first_date.format("%Y-%m-%d"));
+            } else {
+                println!(
+                    "  {} ({} dates, first on {})",
+                    message,
+                    count,
+                    first_date.format("%Y-%m-%d")
+                );
+            }
+        }
+    }
+}
+
+/// Problems between classes: sessions that overlap and back-to-back sessions in different
+/// buildings with less than the travel time between them.
+#[derive(Default)]
+struct Conflicts {
+    overlaps: Findings,
+    travel: Findings,
+}
+
+/// Compares every session of the given (already validated) schedules with the others on the
+/// same date, after exclusions and overrides.
+fn find_conflicts(schedules: &[ClassSchedule], travel_minutes: i64) -> Result<Conflicts> {
+    // Lay out every session by date so classes can be compared with each other
+    let mut by_date: BTreeMap<NaiveDate, Vec<ScheduledSession>> = BTreeMap::new();
+    for schedule in schedules {
+        let resolved = schedule.resolve()?;
+        for date in schedule.get_class_dates()? {
+            for session in resolved.sessions_on(date) {
+                by_date.entry(date).or_default().push(ScheduledSession {
+                    class_name: schedule.class_name.clone(),
+                    start: parse_time(&session.start_time)?,
+                    end: parse_time(&session.end_time)?,
+                    location: session.location,
+                });
+            }
+        }
+    }
+
+    let mut conflicts = Conflicts::default();
+    for (date, sessions) in &mut by_date {
+        sessions.sort_by_key(|s| (s.start, s.end));
+        let weekday = date.format("%A");
+
+        for (i, a) in sessions.iter().enumerate() {
+            for b in &sessions[i + 1..] {
+                if b.start < a.end && a.class_name != b.class_name {
+                    conflicts.overlaps.add(
+                        format!(
+                            "{} {}-{} overlaps {} {}-{} on {}s",
+                            a.class_name,
+                            a.start.format("%H:%M"),
+                            a.end.format("%H:%M"),
+                            b.class_name,
+                            b.start.format("%H:%M"),
+                            b.end.format("%H:%M"),
+                            weekday
+                        ),
+                        *date,
+                    );
+                }
+            }
+        }
+
+        for pair in sessions.windows(2) {
+            let (a, b) = (&pair[0], &pair[1]);
+            let gap = (b.start - a.end).num_minutes();
+            if gap < 0 || gap >= travel_minutes {
+                continue;
+            }
+            let buildings = (
+                a.location.as_deref().and_then(building),
+                b.location.as_deref().and_then(building),
+            );
+            if let (Some(from), Some(to)) = buildings {
+                if from != to {
+                    conflicts.travel.add(
+                        format!(
+                            "Only {} min to get from {} ({}) to {} ({}) on {}s",
+                            gap, a.class_name, from, b.class_name, to, weekday
+                        ),
+                        *date,
+                    );
+                }
+            }
+        }
+    }
+
+    Ok(conflicts)
+}
+
+/// Validates every schedule and compares the valid ones with each other. Fails when a schedule has
+/// errors or classes overlap; travel time warnings are only reported.
+pub fn check_schedules(travel_minutes: i64) -> Result<()> {
+    let mut file_issues: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    let mut schedules = Vec::new();
+
+    for path in list_schedule_files()? {
+        let file_name = path
+            .file_name()
+            .map(|f| f.to_string_lossy().to_string())
+            .unwrap_or_default();
+        match ClassSchedule::from_file(&path.to_string_lossy()) {
+            Ok(schedule) => {
+                let issues = schedule.validate();
+                if issues.is_empty() {
+                    schedules.push(schedule);
+                } else {
+                    file_issues.insert(file_name, issues);
+                }
+            }
+            Err(e) => {
+                file_issues.insert(file_name, vec![format!("{:#}", e)]);
+            }
+        }
+    }
+
+    let Conflicts { overlaps, travel } = find_conflicts(&schedules, travel_minutes)?;
+
+    println!("\nSchedule Check");
+    println!("===================================");
+
+    if file_issues.is_empty() && overlaps.issues.is_empty() && travel.issues.is_empty() {
+        println!("\nNo issues found in {} schedules. ✓", schedules.len());
+        return Ok(());
+    }
+
+    for (file_name, issues) in &file_issues {
+        println!("\n{}:", file_name);
+        for issue in issues {
+            println!("  {}", issue);
+        }
+    }
+
+    if !overlaps.issues.is_empty() {
+        println!("\nOverlapping classes:");
+        overlaps.print();
+    }
+
+    if !travel.issues.is_empty() {
+        println!("\nTravel time warnings:");
+        travel.print();
+    }
+
+    // Classes with problems in their own file aren't compared with the rest
+    if !file_issues.is_empty() {
+        println!(
+            "\nSkipped overlap checks for {} schedule(s) with errors.",
+            file_issues.len()
+        );
+    }
+
+    if !file_issues.is_empty() || !overlaps.issues.is_empty() {
+        anyhow::bail!(
+            "Schedule check failed: {} schedule(s) with errors, {} overlapping class(es)",
+            file_issues.len(),
+            overlaps.issues.len()
+        );
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn schedule(json: &str) -> ClassSchedule {
+        serde_json::from_str(json).unwrap()
+    }
+
+    fn messages(findings: &Findings) -> Vec<(&str, NaiveDate, u32)> {
+        findings
+            .issues
+            .iter()
+            .map(|(message, (date, count))| (message.as_str(), *date, *count))
+            .collect()
+    }
+
+    fn date(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2024, 3, day).unwrap()
+    }
+
+    #[test]
+    fn test_overlapping_sessions() {
+        let schedules = [
+            schedule(
+                r#"{ "class_name": "CS201", "start_date": "2024-03-04", "end_date": "2024-03-15",
+                     "schedule": [{ "weekday": "monday", "start_time": "10:00", "end_time": "11:30" }] }"#,
+            ),
+            schedule(
+                r#"{ "class_name": "MATH241", "start_date": "2024-03-04", "end_date": "2024-03-15",
+                     "schedule": [{ "weekday": "monday", "start_time": "11:00", "end_time": "12:00" }] }"#,
+            ),
+        ];
+
+        let conflicts = find_conflicts(&schedules, 15).unwrap();
+        assert_eq!(
+            messages(&conflicts.overlaps),
+            vec![(
+                "CS201 10:00-11:30 overlaps MATH241 11:00-12:00 on Mondays",
+                date(4),
+                2
+            )]
+        );
+        assert!(conflicts.travel.issues.is_empty());
+    }
+
+    #[test]
+    fn test_back_to_back_in_different_buildings() {
+        let schedules = [
+            schedule(
+                r#"{ "class_name": "CS201", "start_date": "2024-03-04", "end_date": "2024-03-08",
+                     "schedule": [{ "weekday": "monday", "start_time": "10:00", "end_time": "10:50",
+                                    "location": "Siebel Center, Room 1404" }] }"#,
+            ),
+            schedule(
+                r#"{ "class_name": "MATH241", "start_date": "2024-03-04", "end_date": "2024-03-08",
+                     "schedule": [{ "weekday": "monday", "start_time": "11:00", "end_time": "11:50",
+                                    "location": "Altgeld Hall 314" }] }"#,
+            ),
+        ];
+
+        let conflicts = find_conflicts(&schedules, 15).unwrap();
+        assert!(conflicts.overlaps.issues.is_empty());
+        assert_eq!(
+            messages(&conflicts.travel),
+            vec![(
+                "Only 10 min to get from CS201 (Siebel Center) to MATH241 (Altgeld Hall) on Mondays",
+                date(4),
+                1
+            )]
+        );
+        assert!(find_conflicts(&schedules, 10)
+            .unwrap()
+            .travel
+            .issues
+            .is_empty());
+    }
+
+    #[test]
+    fn test_excluded_and_overridden_dates() {
+        let schedules = [
+            schedule(
+                r#"{ "class_name": "CS201", "start_date": "2024-03-04", "end_date": "2024-03-22",
+                     "schedule": [{ "weekday": "monday", "start_time": "10:00", "end_time": "11:00" }],
+                     "overrides": [{ "date": "2024-03-18", "start_time": "13:00", "end_time": "14:00" }] }"#,
+            ),
+            schedule(
+                r#"{ "class_name": "MATH241", "start_date": "2024-03-04", "end_date": "2024-03-22",
+                     "schedule": [{ "weekday": "monday", "start_time": "13:30", "end_time": "14:30" }] }"#,
+            ),
+            schedule(
+                r#"{ "class_name": "PHYS211", "start_date": "2024-03-04", "end_date": "2024-03-22",
+                     "schedule": [{ "weekday": "monday", "start_time": "10:30", "end_time": "11:30" }],
+                     "exclusions": ["2024-03-04", "2024-03-11"] }"#,
+            ),
+        ];
+
+        // PHYS211 is cancelled on the days it would clash with CS201 and only meets once CS201 has
+        // moved to the afternoon, where it clashes with MATH241 instead
+        let conflicts = find_conflicts(&schedules, 15).unwrap();
+        assert_eq!(
+            messages(&conflicts.overlaps),
+            vec![(
+                "CS201 13:00-14:00 overlaps MATH241 13:30-14:30 on Mondays",
+                date(18),
+                1
+            )]
+        );
+    }
+}
@@ -0,0 +1,116 @@
+use chrono::NaiveDate;
+use rand::{seq::SliceRandom, Rng};
+
+/// An item that can be picked, along with how often it should come up and when it was last shown.
+#[derive(Debug, Clone)]
+pub struct Candidate<T> {
+    pub item: T,
+    pub weight: f64,
+    pub last_shown: Option<NaiveDate>,
+}
+
+impl<T> Candidate<T> {
+    pub fn new(item: T) -> Self {
+        Candidate {
+            item,
+            weight: 1.0,
+            last_shown: None,
+        }
+    }
+}
+
+/// Picks a candidate at random, never returning one shown within `window` days of `today`.
+///
+/// Candidates outside the window get a boost the longer they have gone unseen, and candidates
+/// that have never been shown are treated as if they were last seen twice the window ago.
+/// Returns `None` if every candidate is inside the window. Candidates with a weight of zero are
+/// never returned.
+pub fn pick_spaced<'a, T, R: Rng + ?Sized>(
+    candidates: &'a [Candidate<T>],
+    today: NaiveDate,
+    window: u32,
+    rng: &mut R,
+) -> Option<&'a T> {
+    let window = window as i64;
+    let eligible: Vec<(&Candidate<T>, f64)> = candidates
+        .iter()
+        .filter(|c| c.weight > 0.0)
+        .filter_map(|c| {
+            let days_since = c
+                .last_shown
+                .map(|d| (today - d).num_days())
+                .unwrap_or(window * 2);
+            if days_since < window {
+                return None;
+            }
+            Some((c, c.weight * (days_since - window + 1) as f64))
+        })
+        .collect();
+
+    eligible
+        .choose_weighted(rng, |(_, weight)| *weight)
+        .ok()
+        .map(|(c, _)| &c.item)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn test_pick_spaced_skips_recent() {
+        let today = date(2024, 3, 15);
+        let candidates = vec![
+            Candidate {
+                item: "recent",
+                weight: 100.0,
+                last_shown: Some(date(2024, 3, 10)),
+            },
+            Candidate {
+                item: "old",
+                weight: 1.0,
+                last_shown: Some(date(2024, 1, 1)),
+            },
+        ];
+
+        let mut rng = StdRng::seed_from_u64(7);
+        for _ in 0..50 {
+            assert_eq!(pick_spaced(&candidates, today, 14, &mut rng), Some(&"old"));
+        }
+    }
+
+    #[test]
+    fn test_pick_spaced_none_inside_window() {
+        let today = date(2024, 3, 15);
+        let candidates = vec![
+            Candidate {
+                item: "excluded",
+                weight: 0.0,
+                last_shown: None,
+            },
+            Candidate {
+                item: "yesterday",
+                weight: 1.0,
+                last_shown: Some(date(2024, 3, 14)),
+            },
+            Candidate {
+                item: "last week",
+                weight: 1.0,
+                last_shown: Some(date(2024, 3, 8)),
+            },
+        ];
+
+        // Nothing is repeated early, and a zero weight never fills the gap
+        let mut rng = StdRng::seed_from_u64(7);
+        assert_eq!(pick_spaced(&candidates, today, 14, &mut rng), None);
+        assert_eq!(
+            pick_spaced(&candidates, today, 7, &mut rng),
+            Some(&"last week")
+        );
+    }
+}
@@ -0,0 +1,136 @@
+use chrono::{Duration, NaiveDate};
+
+/// Middle value of sorted numbers, averaging the two middle ones for an even count.
+pub fn median(sorted: &[u64]) -> f64 {
+    match sorted.len() {
+        0 => 0.0,
+        n if n % 2 == 1 => sorted[n / 2] as f64,
+        n => (sorted[n / 2 - 1] + sorted[n / 2]) as f64 / 2.0,
+    }
+}
+
+/// Nearest-rank percentile (0-100) of sorted numbers.
+pub fn percentile(sorted: &[u64], p: f64) -> u64 {
+    if sorted.is_empty() {
+        return 0;
+    }
+    let rank = ((p / 100.0) * sorted.len() as f64).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
+
+/// Least-squares slope of values over time, in units per year. `None` with fewer than two
+/// distinct dates.
+pub fn trend_per_year(points: &[(NaiveDate, f64)]) -> Option<f64> {
+    let first = points.iter().map(|(d, _)| *d).min()?;
+    let xs: Vec<f64> = points
+        .iter()
+        .map(|(d, _)| (*d - first).num_days() as f64 / 365.25)
+        .collect();
+    let n = points.len() as f64;
+    let mean_x = xs.iter().sum::<f64>() / n;
+    let mean_y = points.iter().map(|(_, y)| y).sum::<f64>() / n;
+
+    let mut covariance = 0.0;
+    let mut variance = 0.0;
+    for (x, (_, y)) in xs.iter().zip(points) {
+        covariance += (x - mean_x) * (y - mean_y);
+        variance += (x - mean_x) * (x - mean_x);
+    }
+    if variance == 0.0 {
+        None
+    } else {
+        Some(covariance / variance)
+    }
+}
+
+/// Pearson correlation of paired values, from -1 to 1. `None` when either side doesn't vary.
+pub fn correlation(pairs: &[(f64, f64)]) -> Option<f64> {
+    if pairs.len() < 2 {
+        return None;
+    }
+    let n = pairs.len() as f64;
+    let mean_x = pairs.iter().map(|(x, _)| x).sum::<f64>() / n;
+    let mean_y = pairs.iter().map(|(_, y)| y).sum::<f64>() / n;
+
+    let (mut covariance, mut variance_x, mut variance_y) = (0.0, 0.0, 0.0);
+    for (x, y) in pairs {
+        covariance += (x - mean_x) * (y - mean_y);
+        variance_x += (x - mean_x) * (x - mean_x);
+        variance_y += (y - mean_y) * (y - mean_y);
+    }
+    if variance_x == 0.0 || variance_y == 0.0 {
+        None
+    } else {
+        Some(covariance / (variance_x * variance_y).sqrt())
+    }
+}
+
+/// Runs of consecutive days as `(first, last)`, in date order. Expects sorted dates.
+pub fn streaks(dates: &[NaiveDate]) -> Vec<(NaiveDate, NaiveDate)> {
+    let mut runs: Vec<(NaiveDate, NaiveDate)> = Vec::new();
+    for &date in dates {
+        match runs.last_mut() {
+            Some((_, last)) if date == *last => {}
+            Some((_, last)) if date == *last + Duration::days(1) => *last = date,
+            _ => runs.push((date, date)),
+        }
+    }
+    runs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_median_and_percentiles() {
+        assert_eq!(median(&[1, 3, 5]), 3.0);
+        assert_eq!(median(&[1, 3, 5, 7]), 4.0);
+        assert_eq!(median(&[]), 0.0);
+
+        let values: Vec<u64> = (1..=10).collect();
+        assert_eq!(percentile(&values, 10.0), 1);
+        assert_eq!(percentile(&values, 50.0), 5);
+        assert_eq!(percentile(&values, 90.0), 9);
+        assert_eq!(percentile(&values, 100.0), 10);
+        assert_eq!(percentile(&values, 0.0), 1);
+    }
+
+    #[test]
+    fn test_trend_per_year() {
+        let date = |y| NaiveDate::from_ymd_opt(y, 1, 1).unwrap();
+        let points = [
+            (date(2020), 100.0),
+            (date(2022), 300.0),
+            (date(2024), 500.0),
+        ];
+        let slope = trend_per_year(&points).unwrap();
+        assert!((slope - 100.0).abs() < 0.5);
+
+        assert_eq!(
+            trend_per_year(&[(date(2020), 1.0), (date(2020), 5.0)]),
+            None
+        );
+        assert_eq!(trend_per_year(&[]), None);
+    }
+
+    #[test]
+    fn test_correlation() {
+        let rising = [(1.0, 2.0), (2.0, 4.0), (3.0, 6.5)];
+        assert!(correlation(&rising).unwrap() > 0.99);
+        let falling = [(1.0, 5.0), (2.0, 3.0), (3.0, 1.0)];
+        assert!((correlation(&falling).unwrap() + 1.0).abs() < 1e-9);
+        assert_eq!(correlation(&[(1.0, 3.0), (2.0, 3.0)]), None);
+    }
+
+    #[test]
+    fn test_streaks() {
+        let day = |d| NaiveDate::from_ymd_opt(2024, 2, d).unwrap();
+        let dates = [day(1), day(2), day(3), day(5), day(7), day(8)];
+        assert_eq!(
+            streaks(&dates),
+            vec![(day(1), day(3)), (day(5), day(5)), (day(7), day(8))]
+        );
+        assert!(streaks(&[]).is_empty());
+    }
+}
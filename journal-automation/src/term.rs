@@ -0,0 +1,182 @@
+use crate::chart;
+use crate::journal::{class_attendance, get_journal_path_for_date, read_class_notes};
+use crate::schedule::{
+    get_schedules_dir, load_schedules, load_terms, terms_path_for, ClassSchedule, Term,
+};
+use anyhow::{Context, Result};
+use chrono::Local;
+use std::{
+    fs::{self, File},
+    path::Path,
+};
+
+/// Picks the named term, or the one in progress (else the latest to have started) without a name.
+fn find_term(name: Option<&str>) -> Result<Term> {
+    let path = terms_path_for(Path::new(&get_schedules_dir()?));
+    let mut terms = load_terms(&path)?;
+    if terms.is_empty() {
+        anyhow::bail!("No terms found in {}", path.display());
+    }
+
+    let today = Local::now().date_naive();
+    let index = match name {
+        Some(name) => terms
+            .iter()
+            .position(|t| t.name == name)
+            .with_context(|| format!("Unknown term: {}", name))?,
+        None => match terms.iter().position(|t| t.contains(today)) {
+            Some(index) => index,
+            None => {
+                let today = today.format("%Y-%m-%d").to_string();
+                terms
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, t)| t.start_date <= today)
+                    .max_by(|(_, a), (_, b)| a.start_date.cmp(&b.start_date))
+                    .map(|(i, _)| i)
+                    .context("No term has started yet; pass the term name")?
+            }
+        },
+    };
+    Ok(terms.swap_remove(index))
+}
+
+/// Loads every schedule that belongs to the term. Schedules that fail to load are skipped, as
+/// they are everywhere else.
+fn term_schedules(term: &Term) -> Result<Vec<ClassSchedule>> {
+    let schedules: Vec<ClassSchedule> = load_schedules()?
+        .into_iter()
+        .filter(|s| s.term.as_deref() == Some(term.name.as_str()))
+        .collect();
+    if schedules.is_empty() {
+        anyhow::bail!("No schedules belong to {}", term.name);
+    }
+    Ok(schedules)
+}
+
+/// Creates an empty notes file for every class day in the term, leaving existing files alone.
+pub fn create_notes(name: Option<&str>) -> Result<()> {
+    let term = find_term(name)?;
+
+    for schedule in term_schedules(&term)? {
+        let mut created = 0;
+        let dates = schedule.get_class_dates()?;
+        for &date in &dates {
+            let path = get_journal_path_for_date(date, &schedule.class_name)?;
+            if Path::new(&path).exists() {
+                continue;
+            }
+            if let Some(parent) = Path::new(&path).parent() {
+                fs::create_dir_all(parent)?;
+            }
+            File::create(&path).with_context(|| format!("Failed to create {}", path))?;
+            created += 1;
+        }
+        println!(
+            "{}: {} class days, {} new files",
+            schedule.class_name,
+            dates.len(),
+            created
+        );
+    }
+
+    Ok(())
+}
+
+pub fn term_report(name: Option<&str>) -> Result<()> {
+    let term = find_term(name)?;
+    let schedules = term_schedules(&term)?;
+    let today = Local::now().date_naive();
+
+    println!("\n{} ({} to {})", term.name, term.start_date, term.end_date);
+    println!("===================================");
+    println!();
+
+    let max_name_width = schedules
+        .iter()
+        .map(|s| s.class_name.len())
+        .max()
+        .unwrap_or(1);
+
+    for schedule in &schedules {
+        let attendance = class_attendance(schedule, today, |date| {
+            read_class_notes(date, &schedule.class_name)
+        })?;
+        let missing = &attendance.missed;
+
+        let bar = chart::bar(attendance.completion_rate(), 20);
+        let completion_marker = if missing.is_empty() && !attendance.held.is_empty() {
+            " ✓"
+        } else {
+            ""
+        };
+
+        println!(
+            "{:<name_width$} | {:>3}/{:<3} notes | {:>6.1}% {} {} to go{}",
+            schedule.class_name,
+            attendance.attended(),
+            attendance.held.len(),
+            attendance.completion_rate(),
+            bar,
+            attendance.upcoming,
+            completion_marker,
+            name_width = max_name_width
+        );
+        if !missing.is_empty() {
+            let recent: Vec<String> = missing
+                .iter()
+                .rev()
+                .take(5)
+                .map(|d| d.format("%b %d").to_string())
+                .collect();
+            println!(
+                "{:<name_width$}   missing: {}{}",
+                "",
+                recent.join(", "),
+                if missing.len() > 5 { ", ..." } else { "" },
+                name_width = max_name_width
+            );
+        }
+    }
+
+    println!();
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    #[test]
+    fn test_schedule_takes_term_dates_and_breaks() {
+        let term: Term = serde_json::from_str(
+            r#"{
+                "name": "Spring 2024",
+                "start_date": "2024-03-04",
+                "end_date": "2024-03-22",
+                "breaks": [{ "start": "2024-03-09", "end": "2024-03-17", "reason": "Spring break" }]
+            }"#,
+        )
+        .unwrap();
+        let mut schedule: ClassSchedule = serde_json::from_str(
+            r#"{
+                "class_name": "CS201",
+                "term": "Spring 2024",
+                "schedule": [{ "weekday": "monday", "start_time": "10:00", "end_time": "11:00" }]
+            }"#,
+        )
+        .unwrap();
+
+        schedule.apply_term(&term);
+        assert!(schedule.validate().is_empty());
+        assert_eq!(
+            schedule.get_class_dates().unwrap(),
+            vec![
+                NaiveDate::from_ymd_opt(2024, 3, 4).unwrap(),
+                NaiveDate::from_ymd_opt(2024, 3, 18).unwrap(),
+            ]
+        );
+        assert!(term.contains(NaiveDate::from_ymd_opt(2024, 3, 22).unwrap()));
+    }
+}
@@ -1,16 +1,23 @@
+use crate::header::MISSING;
 use anyhow::{Context, Result};
+use chrono::{Datelike, NaiveDate};
 use reqwest::blocking::Client;
 use serde_json::Value;
+use std::io::{self, BufRead, IsTerminal, Write};
 use std::process::Command;
 
+// MAC address and display name of the primary journaling device
+pub const DEVICE_MAC: &str = "fc:e2:6c:18:be:70";
+pub const DEVICE_NAME: &str = "✨ luna";
+
 pub fn get_device_info() -> String {
     let output = Command::new("ifconfig").arg("en0").output();
 
     match output {
         Ok(output) => {
             let output_str = String::from_utf8_lossy(&output.stdout);
-            if output_str.contains("fc:e2:6c:18:be:70") {
-                "✨ luna".to_string()
+            if output_str.contains(DEVICE_MAC) {
+                DEVICE_NAME.to_string()
             } else {
                 "other device".to_string()
             }
@@ -129,6 +136,46 @@ pub fn validate_year(s: &str) -> Result<u32, String> {
     }
 }
 
+/// Parses a 1-5 rating such as a mood.
+pub fn validate_scale(s: &str) -> Result<u8, String> {
+    match s.parse::<u8>() {
+        Ok(value) if (1..=5).contains(&value) => Ok(value),
+        _ => Err("Rating must be a number from 1 to 5".to_string()),
+    }
+}
+
+/// Asks for a 1-5 rating when run from a terminal. Skipped or invalid answers (and scripted runs)
+/// are recorded as missing.
+pub fn prompt_scale(label: &str) -> String {
+    if !io::stdin().is_terminal() {
+        return MISSING.to_string();
+    }
+    print!("{} today (1-5, Enter to skip): ", label);
+    let _ = io::stdout().flush();
+    let mut answer = String::new();
+    if io::stdin().lock().read_line(&mut answer).is_err() {
+        return MISSING.to_string();
+    }
+    match answer.trim().parse::<u8>() {
+        Ok(value) if (1..=5).contains(&value) => value.to_string(),
+        _ => MISSING.to_string(),
+    }
+}
+
+/// The first day of the current month.
+pub fn current_month() -> NaiveDate {
+    let today = chrono::Local::now().date_naive();
+    today - chrono::Duration::days(today.day0() as i64)
+}
+
+/// Parses a `YYYY-MM` month to its first day.
+pub fn validate_month(s: &str) -> Result<NaiveDate, String> {
+    let date = NaiveDate::parse_from_str(&format!("{}-01", s), "%Y-%m-%d")
+        .map_err(|_| "Month must be in YYYY-MM format")?;
+    validate_year(&date.format("%Y").to_string())?;
+    Ok(date)
+}
+
 pub fn get_git_root() -> Result<String> {
     let output = Command::new("git")
         .args(["rev-parse", "--show-toplevel"])
@@ -0,0 +1,232 @@
+use crate::journal::append_to_todays_entry;
+use crate::shuffle::{pick_spaced, Candidate};
+use crate::utils::get_git_root;
+use anyhow::{Context, Result};
+use chrono::{Local, NaiveDate};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    fs::{self, OpenOptions},
+    path::Path,
+};
+
+#[derive(Debug, Clone)]
+pub struct Video {
+    pub name: String,
+    pub weight: f64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct HistoryRecord {
+    date: String, // YYYY-MM-DD
+    video: String,
+}
+
+// Folder inside journal-automation that holds the video list and watch history
+pub const DATA_DIR: &str = "videos";
+
+fn default_list_path() -> Result<String> {
+    Ok(format!(
+        "{}/journal-automation/{}/list.txt",
+        get_git_root()?,
+        DATA_DIR
+    ))
+}
+
+fn history_path() -> Result<String> {
+    Ok(format!(
+        "{}/journal-automation/{}/history.csv",
+        get_git_root()?,
+        DATA_DIR
+    ))
+}
+
+/// Parses a video list: one video per line, optionally followed by `| weight`.
+/// Blank lines and lines starting with `#` are ignored.
+fn parse_video_list(contents: &str) -> Result<Vec<Video>> {
+    let mut videos = Vec::new();
+
+    for line in contents.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let (name, weight) = match line.rsplit_once('|') {
+            Some((name, weight)) => {
+                let weight = weight
+                    .trim()
+                    .parse::<f64>()
+                    .with_context(|| format!("Invalid weight in video list: {}", line))?;
+                (name.trim(), weight)
+            }
+            None => (line, 1.0),
+        };
+
+        videos.push(Video {
+            name: name.to_string(),
+            weight,
+        });
+    }
+
+    Ok(videos)
+}
+
+fn load_video_list(path: &str) -> Result<Vec<Video>> {
+    let contents =
+        fs::read_to_string(path).with_context(|| format!("Failed to read video list: {}", path))?;
+    parse_video_list(&contents)
+}
+
+fn load_history(path: &str) -> Result<Vec<(NaiveDate, String)>> {
+    if !Path::new(path).exists() {
+        return Ok(Vec::new());
+    }
+
+    let mut reader = csv::Reader::from_path(path)
+        .with_context(|| format!("Failed to read video history: {}", path))?;
+    let mut history = Vec::new();
+    for record in reader.deserialize() {
+        let record: HistoryRecord = record?;
+        let date = NaiveDate::parse_from_str(&record.date, "%Y-%m-%d")
+            .with_context(|| format!("Invalid date in video history: {}", record.date))?;
+        history.push((date, record.video));
+    }
+
+    Ok(history)
+}
+
+fn append_history(path: &str, date: NaiveDate, video: &str) -> Result<()> {
+    let is_new = !Path::new(path).exists();
+    if let Some(parent) = Path::new(path).parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    let file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .with_context(|| format!("Failed to open video history: {}", path))?;
+    let mut writer = csv::WriterBuilder::new()
+        .has_headers(is_new)
+        .from_writer(file);
+    writer.serialize(HistoryRecord {
+        date: date.format("%Y-%m-%d").to_string(),
+        video: video.to_string(),
+    })?;
+    writer.flush()?;
+
+    Ok(())
+}
+
+pub fn next_video(list: Option<&str>, window: u32) -> Result<()> {
+    let list_path = match list {
+        Some(path) => path.to_string(),
+        None => default_list_path()?,
+    };
+    let videos = load_video_list(&list_path)?;
+    if videos.is_empty() {
+        anyhow::bail!("No videos found in {}", list_path);
+    }
+
+    let history_path = history_path()?;
+    let history = load_history(&history_path)?;
+    let mut last_shown: HashMap<&str, NaiveDate> = HashMap::new();
+    for (date, video) in &history {
+        let entry = last_shown.entry(video.as_str()).or_insert(*date);
+        if *date > *entry {
+            *entry = *date;
+        }
+    }
+
+    let candidates: Vec<Candidate<&Video>> = videos
+        .iter()
+        .map(|video| Candidate {
+            item: video,
+            weight: video.weight,
+            last_shown: last_shown.get(video.name.as_str()).copied(),
+        })
+        .collect();
+
+    let today = Local::now().date_naive();
+    let video = pick_spaced(&candidates, today, window, &mut rand::thread_rng())
+        .ok_or_else(|| {
+            anyhow::anyhow!(
+                "Every video was shown in the last {} days; add more to {} or pass a shorter --window",
+                window,
+                list_path
+            )
+        })?;
+
+    append_history(&history_path, today, &video.name)?;
+    append_to_todays_entry(&format!("## Video\n\n- {}", video.name))?;
+
+    println!("Today's video: {}", video.name);
+    Ok(())
+}
+
+pub fn video_stats(list: Option<&str>) -> Result<()> {
+    let list_path = match list {
+        Some(path) => path.to_string(),
+        None => default_list_path()?,
+    };
+    let videos = load_video_list(&list_path)?;
+    let history = load_history(&history_path()?)?;
+
+    // (times shown, last shown)
+    let mut stats: HashMap<&str, (u32, Option<NaiveDate>)> = videos
+        .iter()
+        .map(|video| (video.name.as_str(), (0, None)))
+        .collect();
+    for (date, video) in &history {
+        let entry = stats.entry(video.as_str()).or_insert((0, None));
+        entry.0 += 1;
+        entry.1 = entry.1.max(Some(*date));
+    }
+
+    let mut rows: Vec<_> = stats.into_iter().collect();
+    rows.sort_by(|a, b| b.1 .0.cmp(&a.1 .0).then(a.0.cmp(b.0)));
+
+    println!("\nVideo Statistics");
+    println!("===================================");
+    println!();
+
+    let max_count_width = rows
+        .iter()
+        .map(|(_, (count, _))| count.to_string().len())
+        .max()
+        .unwrap_or(1);
+
+    for (video, (count, last)) in rows {
+        let last = last
+            .map(|d| d.format("%Y-%m-%d").to_string())
+            .unwrap_or_else(|| "never".to_string());
+        println!(
+            "{:>width$}x | last shown {} | {}",
+            count,
+            last,
+            video,
+            width = max_count_width
+        );
+    }
+
+    println!();
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_video_list() {
+        let contents = "# favourites\nhttps://youtu.be/abc\n\nMorning stretch | 2.5\n";
+        let videos = parse_video_list(contents).unwrap();
+
+        assert_eq!(videos.len(), 2);
+        assert_eq!(videos[0].name, "https://youtu.be/abc");
+        assert_eq!(videos[0].weight, 1.0);
+        assert_eq!(videos[1].name, "Morning stretch");
+        assert_eq!(videos[1].weight, 2.5);
+    }
+}